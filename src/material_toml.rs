@@ -1,23 +1,148 @@
 //! Provides the [`MaterialToml`] data type for easily loading materials without requiring a recompile.
 
-use bevy::asset::AssetServer;
-use bevy::color::{Color, LinearRgba};
-use bevy::log::error;
-use bevy::math::{Mat2, Vec2};
-use bevy::pbr::{ExtendedMaterial, MaterialExtension, ParallaxMappingMethod, StandardMaterial};
-use bevy::prelude::default;
-use bevy::reflect::erased_serde::__private::serde::{Deserialize, Serialize};
-use bevy::render::texture::{ImageAddressMode, ImageFilterMode, ImageLoaderSettings, ImageSampler, ImageSamplerDescriptor};
+use crate::utils::levenshtein_distance;
+use bevy::app::{App, Plugin, Update};
+use bevy::asset::io::{AssetSourceId, Reader};
+use bevy::asset::{AssetApp, AssetId, AssetLoader, AssetPath, AssetServer, Assets, AsyncReadExt, Handle, LoadContext};
+use bevy::color::{Color, ColorToComponents, LinearRgba, Srgba};
+use bevy::ecs::event::{Event, EventReader, EventWriter};
+use bevy::ecs::schedule::IntoSystemConfigs;
+use bevy::ecs::system::{Local, Res, ResMut, Resource};
+use bevy::log::{debug, error, warn};
+use bevy::math::{Mat2, Rect, Vec2};
+use bevy::pbr::{ExtendedMaterial, Lightmap, MaterialExtension, ParallaxMappingMethod, StandardMaterial, UvChannel};
+use bevy::prelude::{default, AlphaMode, Image};
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::sprite::ColorMaterial;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::render::texture::{
+	CompressedImageFormats, ImageAddressMode, ImageFilterMode, ImageLoaderSettings, ImageSampler, ImageSamplerDescriptor, ImageType,
+};
+use bevy::time::{Real, Time};
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Display;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// Label of the texture packed from `rough` and `metal` by the [`MaterialTomlLoader`].
+const METALLIC_ROUGHNESS_LABEL: &str = "metallic_roughness";
+
+/// Descriptions of every [`MaterialToml`] field for [`MaterialToml::template`], in the order they are written.
+const TEMPLATE_FIELDS: &[(&str, &str)] = &[
+	("color", "Base color as a hex string like \"#ff8000\", or `{ srgb = [r, g, b, a] }` or `{ linear = [r, g, b, a] }` where the alpha is optional."),
+	("alpha", "How the alpha channel is used: \"opaque\", { mask = cutoff }, \"blend\", \"premultiplied\", \"add\", or \"multiply\".\n\"blend\" if unset and the alpha of `color` is below 1, \"opaque\" otherwise."),
+	("alpha_cutoff", "Overrides the cutoff of `alpha = { mask = .. }`, ignored for other alpha modes."),
+	("extension", "File extension of every texture, or a list where the first existing file is used.\n\"png\" if unset."),
+	("extends", "Path of a material toml to inherit unset fields from.\nInherited textures are loaded from the directory of the toml that set the field loading them."),
+	("tile", "Enables texture tiling."),
+	("unlit", "Ignores lighting."),
+	("normal", "Loads `normal`, \"OpenGL\" for Y+ normal maps or \"DirectX\" for Y- normal maps which are flipped."),
+	("rough", "Perceptual roughness from 0 to 1.\nLoads the textures decided by `pbr_layout`."),
+	("metal", "Metallic from 0 to 1.\nLoads the textures decided by `pbr_layout`."),
+	("pbr_layout", "Which textures `rough` and `metal` load: \"combo_0rm\", \"orm\" with ambient occlusion in red, or \"separate\" for `rough` and `metal`.\n\"combo_0rm\" if unset."),
+	("reflectance", "Specular intensity of non-metals, 0.5 if unset."),
+	("ao", "Loads `ao`, ignored with `pbr_layout = \"orm\"`."),
+	("anisotropy", "Strength of anisotropic specular, for brushed metal and hair.\nLoads `anisotropy`."),
+	("anisotropy_rotation", "Counter-clockwise rotation of the anisotropy direction in radians."),
+	("clearcoat", "Strength of a clear layer on top of the material, like car paint.\nLoads `clearcoat`."),
+	("clearcoat_normal", "Normal map of the clearcoat: \"CustomNormal\" loads `clearcoat_normal`, \"BaseNormal\" reuses `normal`."),
+	("clearcoat_rough", "Roughness of the clearcoat.\nLoads `clearcoat_rough`."),
+	("depth", "Parallax mapping depth scale.\nLoads `depth`."),
+	("depth_bias", "Depth bias, for decals on co-planar geometry."),
+	("depth_hq", "Uses linear filtering for the depth map instead of nearest neighbor, at a cost to performance."),
+	("depth_layers", "Max layer count of parallax mapping, 16 if unset."),
+	("depth_method", "Parallax mapping method: \"occlusion\" or { relief = { max_steps = 8 } }.\n\"occlusion\" if unset."),
+	("emissive", "Emissive lighting.\nLoads `emissive`."),
+	("emissive_color", "Emissive lighting color, in the same forms as `color` with the alpha ignored."),
+	("emissive_exposure", "How much exposure impacts the emissive light, 0 keeps it always bright.\n1 if unset."),
+	("specular_trans", "Specular transmission for glass-like materials.\nLoads `specular_trans`."),
+	("diffuse_transmission", "Fraction of light passing diffusely through the material, for leaves and paper."),
+	("thickness", "Thickness of the volume beneath the surface.\nLoads `thickness`."),
+	("ior", "Index of refraction, 1.5 for glass if unset."),
+	("attenuation_color", "Color white light turns into as it passes through the volume, in the same forms as `color`."),
+	("attenuation_distance", "Distance light travels through the volume before turning into `attenuation_color`."),
+	("lightmap", "Baked lighting, only loaded by `MaterialToml::load_with_extras`.\nLoads `lightmap`."),
+	("lightmap_exposure", "Exposure of the lightmap, 1 if unset."),
+	("lightmap_uv_rect", "The [min, max] corners of the rectangle in the lightmap the UVs are relative to, for atlases."),
+	("uv_channel", "UV channel of every texture, 0 or 1.\n0 if unset."),
+	("uv_channels", "Overrides of `uv_channel` for specific textures, keyed by their file stem."),
+	("uv_offset", "Offset of the UVs as [x, y]."),
+	("uv_rotation", "Counter-clockwise rotation of the UVs in radians, applied after `uv_scale`."),
+	("uv_scale", "Scale of the UVs as [x, y]."),
+	("sampler", "Sampler settings of every texture, with overrides for specific textures keyed by their file stem.\nSettings are `address_mode` (\"repeat\", \"clamp\", or \"mirror\"), `anisotropy` which makes every filter linear above 1, `filter`, and `mipmap_filter` (\"nearest\" or \"linear\")."),
+	("variants", "Named variants which overlay their fields on the rest of the toml."),
+	("extra", "Arbitrary keys kept for tooling, as unknown fields are otherwise an error."),
+];
+
+/// Loads the textures requested by [`MaterialToml::load_with`].
+/// Implemented for [`AssetServer`] and [`LoadContext`].
+pub trait LoadMaterialTomlTexture {
+	/// Loads the grayscale `rough` and `metal` images packed into one image laid out like `combo_0rm`.
+	fn load_metallic_roughness(&mut self, rough: AssetPath<'static>, metal: AssetPath<'static>, sampler: Option<ImageSamplerDescriptor>) -> Handle<Image>;
+
+	/// Loads the image at the `path`, using the `sampler` instead of the default if provided.
+	fn load_material_texture(&mut self, path: AssetPath<'static>, sampler: Option<ImageSamplerDescriptor>) -> Handle<Image>;
+}
+
+impl LoadMaterialTomlTexture for &AssetServer {
+	fn load_metallic_roughness(&mut self, rough: AssetPath<'static>, metal: AssetPath<'static>, sampler: Option<ImageSamplerDescriptor>) -> Handle<Image> {
+		let asset_server = AssetServer::clone(self);
+
+		self.add_async(async move {
+			let rough_bytes = read_asset_bytes(&asset_server, &rough).await?;
+			let metal_bytes = read_asset_bytes(&asset_server, &metal).await?;
+
+			pack_metallic_roughness((rough.path(), &rough_bytes), (metal.path(), &metal_bytes), sampler)
+		})
+	}
+
+	fn load_material_texture(&mut self, path: AssetPath<'static>, sampler: Option<ImageSamplerDescriptor>) -> Handle<Image> {
+		match sampler {
+			None => self.load(path),
+
+			Some(sampler) => self.load_with_settings(path, move |settings: &mut ImageLoaderSettings| {
+				settings.sampler = ImageSampler::Descriptor(sampler.clone());
+			}),
+		}
+	}
+}
+
+impl LoadMaterialTomlTexture for &mut LoadContext<'_> {
+	/// Returns the handle of the image the [`MaterialTomlLoader`] packed before loading the material.
+	fn load_metallic_roughness(&mut self, _rough: AssetPath<'static>, _metal: AssetPath<'static>, _sampler: Option<ImageSamplerDescriptor>) -> Handle<Image> {
+		self.get_label_handle(METALLIC_ROUGHNESS_LABEL)
+	}
+
+	fn load_material_texture(&mut self, path: AssetPath<'static>, sampler: Option<ImageSamplerDescriptor>) -> Handle<Image> {
+		match sampler {
+			None => self.load(path),
+
+			Some(sampler) => self
+				.loader()
+				.with_settings(move |settings: &mut ImageLoaderSettings| {
+					settings.sampler = ImageSampler::Descriptor(sampler.clone());
+				})
+				.load(path),
+		}
+	}
+}
 
 /// Implements `load_material` and `load_material_base` functions for [`AssetServer`]
 /// to make loading materials from their [`MaterialToml`] easier.
 pub trait LoadStandardMaterial {
 	/// Convenience function for loading a [`MaterialToml`] and immediately loading
-	/// a [`StandardMaterial`] from it. fn load_material(&self, path: impl Into<PathBuf>) -> StandardMaterial;
-	fn load_material(&self, path: impl Into<PathBuf>) -> StandardMaterial;
+	/// a [`StandardMaterial`] from it.
+	/// Logs an error and returns the default material if it fails.
+	fn load_material(&self, path: impl Into<PathBuf>) -> StandardMaterial {
+		self.try_load_material(path).unwrap_or_else(|error| {
+			error!("LoadStandardMaterial::load_material failed: {error}");
+
+			StandardMaterial::default()
+		})
+	}
 
 	/// Convenience function for loading a [`MaterialToml`] and immediately loading
 	/// a [`ExtendedMaterial`] with [`StandardMaterial`] as the base.
@@ -27,31 +152,73 @@ pub trait LoadStandardMaterial {
 			extension,
 		}
 	}
+
+	/// Same as [`LoadStandardMaterial::load_material`] but returns the error instead of a fallback.
+	fn try_load_material(&self, path: impl Into<PathBuf>) -> Result<StandardMaterial, MaterialTomlError>;
 }
 
 impl LoadStandardMaterial for AssetServer {
-	fn load_material(&self, path: impl Into<PathBuf>) -> StandardMaterial {
-		let path = path.into();
+	fn try_load_material(&self, path: impl Into<PathBuf>) -> Result<StandardMaterial, MaterialTomlError> {
+		MaterialToml::new(path)?.try_load(self)
+	}
+}
 
-		MaterialToml::new(&path)
-			.unwrap_or_else(|_| {
-				error!(
-					"<AssetServer as LoadStandardMaterial>::load_material failed to load a MaterialToml at path {path:?} CANON: {:?}",
-					path.canonicalize()
-				);
+/// Same as [`LoadStandardMaterial`] but for the [`ColorMaterial`] of 2D meshes.
+pub trait LoadColorMaterial {
+	/// Convenience function for loading a [`MaterialToml`] and immediately loading
+	/// a [`ColorMaterial`] from it.
+	/// Logs an error and returns the default material if it fails.
+	fn load_color_material(&self, path: impl Into<PathBuf>) -> ColorMaterial {
+		self.try_load_color_material(path).unwrap_or_else(|error| {
+			error!("LoadColorMaterial::load_color_material failed: {error}");
 
-				MaterialToml::default()
-			})
-			.load(&self)
+			ColorMaterial::default()
+		})
+	}
+
+	/// Same as [`LoadColorMaterial::load_color_material`] but returns the error instead of a fallback.
+	fn try_load_color_material(&self, path: impl Into<PathBuf>) -> Result<ColorMaterial, MaterialTomlError>;
+}
+
+impl LoadColorMaterial for AssetServer {
+	fn try_load_color_material(&self, path: impl Into<PathBuf>) -> Result<ColorMaterial, MaterialTomlError> {
+		MaterialToml::new(path)?.try_load_color_material(self)
 	}
 }
 
 /// Configuration for loading materials with multiple textures and custom settings.
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct MaterialToml {
+	/// How the alpha channel of the material is used.
+	/// If `None` and the alpha of `color` is below 1, `Blend` is used.
+	/// Does not load any textures.
+	pub alpha: Option<MaterialTomlAlphaMode>,
+
+	/// Overrides the cutoff of `alpha = { mask = .. }`, ignored with a warning for other alpha modes.
+	/// Does not load any textures.
+	pub alpha_cutoff: Option<f32>,
+
+	/// Strength of anisotropic specular, for brushed metal and hair.
+	/// Loads `anisotropy` with the direction in red and green mapped from [-1, 1] to [0, 1],
+	/// and the strength multiplier in blue.
+	pub anisotropy: Option<f32>,
+
+	/// Counter-clockwise rotation of the anisotropy direction in radians.
+	/// Does not load any textures.
+	pub anisotropy_rotation: Option<f32>,
+
 	/// Loads `ao`.
 	pub ao: Option<bool>,
 
+	/// Color white light turns into as it passes through the volume, see [`MaterialTomlColor`] for the accepted forms.
+	/// Does not load any textures.
+	pub attenuation_color: Option<MaterialTomlColor>,
+
+	/// Distance light travels through the volume before turning into `attenuation_color`.
+	/// Does not load any textures.
+	pub attenuation_distance: Option<f32>,
+
 	/// Loads `clearcoat`.
 	pub clearcoat: Option<f32>,
 
@@ -62,16 +229,21 @@ pub struct MaterialToml {
 	/// Loads `clearcoat_rough`.
 	pub clearcoat_rough: Option<f32>,
 
-	/// Base color for the material in LinearRGB or LinearRGBA color space.
+	/// Base color for the material, see [`MaterialTomlColor`] for the accepted forms.
 	/// Does not load any textures.
-	pub color: Option<(f32, f32, f32, Option<f32>)>,
+	pub color: Option<MaterialTomlColor>,
 
 	/// Parallax mapping.
-	/// Loads `height`.
+	/// Loads `depth`.
 	pub depth: Option<f32>,
 
-	/// Set to `Some(false)` or `None` to use nearest neighbor in depth-map sampling.
-	/// Can be a major deficit to performance.
+	/// Direct binding to [`StandardMaterial`] `depth_bias`, for decals on co-planar geometry.
+	/// Does not load any textures.
+	pub depth_bias: Option<f32>,
+
+	/// Set to `Some(true)` to use linear filtering in depth-map sampling, instead of nearest neighbor.
+	/// Either filter keeps the address mode of `tile`, and `[sampler.depth]` overrides both.
+	/// Linear filtering can be a major deficit to performance.
 	/// Does not load any textures.
 	pub depth_hq: Option<bool>,
 
@@ -79,17 +251,23 @@ pub struct MaterialToml {
 	/// Does not load any textures.
 	pub depth_layers: Option<f32>,
 
-	/// The rendering method for parallax mapping.
+	/// The rendering method for parallax mapping, `"occlusion"` if `None`.
+	/// A bare integer is deprecated but still accepted as the max steps of `relief`.
+	/// Does not load any textures.
+	pub depth_method: Option<MaterialTomlParallaxMethod>,
+
+	/// Fraction of light passing diffusely through the material, for leaves and paper.
 	/// Does not load any textures.
-	pub depth_method: Option<u32>,
+	pub diffuse_transmission: Option<f32>,
 
 	/// Emissive lighting.
 	/// Loads `emissive` texture.
 	pub emissive: Option<bool>,
 
-	/// Emissive lighting color in LinearRGB or LinearRGBA color space.
+	/// Emissive lighting color, see [`MaterialTomlColor`] for the accepted forms.
+	/// The alpha is ignored.
 	/// Loads `emissive` texture.
-	pub emissive_color: Option<(f32, f32, f32)>,
+	pub emissive_color: Option<MaterialTomlColor>,
 
 	/// How much exposure impacts the emissive light of this material.
 	/// Defaults to 1 which is best for realistic-light emitting materials.
@@ -97,33 +275,92 @@ pub struct MaterialToml {
 	/// Does not load any textures.
 	pub emissive_exposure: Option<f32>,
 
-	/// File extension for all texture files.
-	pub extension: Option<String>,
+	/// Path of a material toml to inherit unset fields from, resolved the same way as [`MaterialToml::new`].
+	/// Inherited textures are loaded from the directory of the toml that set the field loading them.
+	pub extends: Option<PathBuf>,
+
+	/// Arbitrary keys kept for tooling, as unknown fields are otherwise an error.
+	pub extra: Option<toml::Table>,
+
+	/// File extension for all texture files, or a list of them where the first existing file is used.
+	/// `"png"` if `None`.
+	pub extension: Option<MaterialTomlExtension>,
+
+	/// Index of refraction, 1.5 for glass if `None`.
+	/// Does not load any textures.
+	pub ior: Option<f32>,
+
+	/// Baked lighting, which bevy applies with the separate [`Lightmap`] component.
+	/// Loads `lightmap`, but only with [`MaterialToml::load_with_extras`].
+	pub lightmap: Option<bool>,
 
-	/// Loads `combo_0rm`.
+	/// Direct binding to [`StandardMaterial`] `lightmap_exposure`.
+	/// Does not load any textures.
+	pub lightmap_exposure: Option<f32>,
+
+	/// The `[min, max]` corners of the rectangle in the lightmap the UVs are relative to, for atlases.
+	/// Does not load any textures.
+	pub lightmap_uv_rect: Option<[Vec2; 2]>,
+
+	/// Loads the textures decided by `pbr_layout`.
 	pub metal: Option<f32>,
 
-	/// Loads `combo_0rm`.
+	/// Loads the textures decided by `pbr_layout`.
 	pub rough: Option<f32>,
 
 	/// Loads `normal`, and the enum decides if we should flip the y axis.
 	pub normal: Option<MaterialTomlNormalsYDir>,
 
+	/// Which textures `rough` and `metal` load, `"combo_0rm"` if `None`.
+	pub pbr_layout: Option<MaterialTomlPbrLayout>,
+
 	/// Does not load any textures.
 	pub reflectance: Option<f32>,
 
+	/// Sampler settings for every texture, and overrides for specific textures in tables named after their file stem.
+	/// Overrides take precedence over `tile` and the nearest-neighbor depth-map sampling.
+	/// Does not load any textures.
+	pub sampler: Option<MaterialTomlSampler>,
+
 	/// Specular TRANSMISSION not reflection (for glass-like materials) so use rough instead.
 	/// Loads `specular_trans`.
 	pub specular_trans: Option<f32>,
 
+	/// Thickness of the volume beneath the surface, for refraction and attenuation.
+	/// Loads `thickness` with the thickness in green.
+	pub thickness: Option<f32>,
+
 	/// Settings to `Some(true)` enables texture tiling.
 	/// Does not load any textures.
 	pub tile: Option<bool>, //
 
+	/// Setting to `Some(true)` ignores lighting.
+	/// Does not load any textures.
+	pub unlit: Option<bool>,
+
+	/// UV channel for every texture, `0` if `None`.
+	/// Does not load any textures.
+	pub uv_channel: Option<MaterialTomlUvChannel>,
+
+	/// Overrides of `uv_channel` for specific textures, keyed by their file stem.
+	/// Does not load any textures.
+	pub uv_channels: Option<BTreeMap<String, MaterialTomlUvChannel>>,
+
 	pub uv_offset: Option<Vec2>,
 
+	/// Counter-clockwise rotation of the UVs in radians, applied after `uv_scale`.
+	pub uv_rotation: Option<f32>,
+
 	pub uv_scale: Option<Vec2>,
 
+	/// Named variants in `[variants.<name>]` tables which overlay their fields on the rest of the toml.
+	/// See [`MaterialToml::variant`].
+	pub variants: Option<BTreeMap<String, MaterialToml>>,
+
+	/// Directories of the tomls that textures were inherited from via `extends`, keyed by the field loading them.
+	#[serde(skip)]
+	pub inherited_dirs: BTreeMap<&'static str, PathBuf>,
+
 	/// The path where the material toml was loaded from, or should be saved to.
 	#[serde(skip)]
 	pub path: Option<PathBuf>,
@@ -131,17 +368,10 @@ pub struct MaterialToml {
 
 impl MaterialToml {
 	pub fn toml_path(&self) -> Option<PathBuf> {
-		let path = self.path.clone()?;
-
-		Some(if path.is_relative() {
-			PathBuf::from("assets").join(&path)
-		} else {
-			path.clone()
-		})
+		Some(disk_path(self.path.as_ref()?))
 	}
 
-	/// # Panics
-	/// If the path field is `None` or the path has no parent.
+	/// The directory of the toml, or `None` if the path field is `None` or has no parent.
 	pub fn dir(&self) -> Option<&Path> {
 		let path_ref: &Path = self.path.as_ref()?.as_ref();
 
@@ -151,74 +381,236 @@ impl MaterialToml {
 	/// An example material toml with a bunch of fields set to arbitrary values.
 	pub fn example() -> Self {
 		Self {
+			alpha: Some(MaterialTomlAlphaMode::Blend),
+			alpha_cutoff: None,
+			anisotropy: Some(0.5),
+			anisotropy_rotation: Some(0.),
 			ao: Some(true),
+			attenuation_color: Some(MaterialTomlColor::Linear(LinearRgba::WHITE)),
+			attenuation_distance: Some(f32::INFINITY),
 			clearcoat: Some(1.0),
 			clearcoat_normal: Some(MaterialTomlClearcoatMode::CustomNormal),
 			clearcoat_rough: Some(1.0),
-			color: Some((0.25, 0.8125, 0.125, Some(0.625))),
+			color: Some(MaterialTomlColor::Hex(Srgba::rgba_u8(0x8a, 0x5a, 0x2b, 0xa0))),
 			depth: Some(0.0025),
+			depth_bias: Some(0.),
 			depth_hq: Some(false),
 			depth_layers: Some(16.),
-			depth_method: Some(8),
+			depth_method: Some(MaterialTomlParallaxMethod::Relief { max_steps: 8 }),
+			diffuse_transmission: Some(0.),
 			emissive: Some(true),
-			emissive_color: Some((1., 1., 1.)),
+			emissive_color: Some(MaterialTomlColor::Linear(LinearRgba::WHITE)),
 			emissive_exposure: Some(1.0),
+			extends: None,
+			extra: None,
+			ior: Some(1.5),
+			lightmap: Some(false),
+			lightmap_exposure: Some(1.),
+			lightmap_uv_rect: None,
 			extension: None,
 			metal: Some(1.),
 			reflectance: Some(0.5),
 			rough: Some(1.),
+			sampler: Some(MaterialTomlSampler {
+				base: MaterialTomlSamplerSettings {
+					filter: Some(MaterialTomlFilter::Linear),
+					..default()
+				},
+
+				maps: BTreeMap::from([(
+					String::from("normal"),
+					MaterialTomlSamplerSettings {
+						anisotropy: Some(16),
+						..default()
+					},
+				)]),
+			}),
 			normal: Some(MaterialTomlNormalsYDir::OpenGL),
+			pbr_layout: Some(MaterialTomlPbrLayout::Combo0rm),
 			specular_trans: Some(0.5),
+			thickness: Some(0.),
 			tile: Some(false),
+			unlit: Some(false),
+			uv_channel: Some(MaterialTomlUvChannel::Uv0),
+			uv_channels: Some(BTreeMap::from([(String::from("ao"), MaterialTomlUvChannel::Uv1)])),
 			uv_offset: Some(Vec2::new(0., 0.)),
+			uv_rotation: Some(0.),
 			uv_scale: Some(Vec2::new(1., 1.)),
+			variants: None,
+			inherited_dirs: BTreeMap::new(),
 			path: None,
 		}
 	}
 
 	/// Creates a new [`StandardMaterial`] from the [`MaterialToml`]'s settings and textures.
+	/// Ignores the `lightmap` field, use [`MaterialToml::load_with_extras`] for it.
 	/// # Panics
 	/// If the path field is `None` or has no parent.
+	/// Use [`MaterialToml::try_load`] to handle that instead.
 	pub fn load(&self, asset_server: &AssetServer) -> StandardMaterial {
-		let mut descriptor = ImageSamplerDescriptor::default();
-		let mut descriptor_changed = false;
-		let dir = self.dir();
-
-		let fn_asset_path = |stem: &'static str| -> Option<PathBuf> {
-			Some(
-				dir?.join(stem)
-					.with_extension(self.extension.as_ref().map(<String as AsRef<str>>::as_ref).unwrap_or("png")),
-			)
-		};
+		self.try_load(asset_server).expect("MaterialToml::load requires a path field with a parent")
+	}
 
-		if self.tile == Some(true) {
-			descriptor.address_mode_u = ImageAddressMode::Repeat;
-			descriptor.address_mode_v = ImageAddressMode::Repeat;
-			descriptor.address_mode_w = ImageAddressMode::Repeat;
-			descriptor_changed = true;
-		}
-
-		//now arc it!
-		//this will let us safely "extend" the lifetime of the descriptor
-		let descriptor_arc: Option<Arc<ImageSamplerDescriptor>> = if descriptor_changed { Some(Arc::new(descriptor)) } else { None };
-
-		//*
-		let fn_load = |stem: &'static str| {
-			fn_asset_path(stem).map(|path|
-			//if we have a non-default ImageSamplerDescriptor,
-			//we need to do some funky stuff to safely send it without degenerating the closure into an FnOnce implementer
-			if let Some(ref descriptor_ref) = descriptor_arc {
-				let descriptor_send = Arc::clone(descriptor_ref);
-
-				asset_server.load_with_settings(path, move |settings: &mut ImageLoaderSettings| {
-					settings.sampler = ImageSampler::Descriptor(descriptor_send.as_ref().clone());
-				})
-			} else {
-				asset_server.load(path)
+	/// Same as [`MaterialToml::load`] but also loads what bevy keeps outside of [`StandardMaterial`].
+	/// # Panics
+	/// If the path field is `None` or has no parent.
+	pub fn load_with_extras(&self, asset_server: &AssetServer) -> (StandardMaterial, MaterialTomlExtras) {
+		(self.load(asset_server), self.extras_with(asset_server))
+	}
+
+	/// Loads the [`MaterialTomlExtras`] with the `loader`.
+	fn extras_with(&self, mut loader: impl LoadMaterialTomlTexture) -> MaterialTomlExtras {
+		let mut extras = MaterialTomlExtras::default();
+
+		if self.lightmap == Some(true) {
+			extras.lightmap = self.texture_asset_path("lightmap").map(|path| Lightmap {
+				image: loader.load_material_texture(path, self.sampler_of("lightmap")),
+				uv_rect: match self.lightmap_uv_rect {
+					Some([min, max]) => Rect::from_corners(min, max),
+					None => Rect::new(0., 0., 1., 1.),
+				},
+			});
+		}
+
+		extras
+	}
+
+	/// Same as [`MaterialToml::load`] but for the [`MaterialToml::variant`] with the `name`.
+	/// Returns `None` if there is no such variant.
+	/// # Panics
+	/// If the path field is `None` or has no parent.
+	pub fn load_variant(&self, asset_server: &AssetServer, name: &str) -> Option<StandardMaterial> {
+		Some(self.variant(name)?.load(asset_server))
+	}
+
+	/// Creates a new [`ColorMaterial`] from the `color` field and texture, using the `sampler` and `tile` settings.
+	/// Every other field only applies to [`StandardMaterial`], and is skipped with a debug log.
+	/// This includes `alpha` as 2D meshes are always alpha blended.
+	/// # Panics
+	/// If the path field is `None` or has no parent.
+	pub fn load_color_material(&self, asset_server: &AssetServer) -> ColorMaterial {
+		self.try_load_color_material(asset_server).expect("MaterialToml::load_color_material requires a path field with a parent")
+	}
+
+	/// Same as [`MaterialToml::load_color_material`] but returns [`MaterialTomlError::MissingPath`] instead of panicking.
+	pub fn try_load_color_material(&self, asset_server: &AssetServer) -> Result<ColorMaterial, MaterialTomlError> {
+		self.load_dir()?;
+
+		Ok(self.load_color_material_with(asset_server))
+	}
+
+	/// Same as [`MaterialToml::load_color_material`] but textures are loaded with the `loader`.
+	pub fn load_color_material_with(&self, mut loader: impl LoadMaterialTomlTexture) -> ColorMaterial {
+		//the serialized table only has the fields that were set
+		if let Ok(table) = toml::Table::try_from(self) {
+			let skipped: Vec<&str> = table
+				.keys()
+				.map(String::as_str)
+				.filter(|key| !matches!(*key, "color" | "extends" | "extension" | "extra" | "sampler" | "tile" | "variants"))
+				.collect();
+
+			if !skipped.is_empty() {
+				debug!("MaterialToml {:?} skipped fields unsupported by ColorMaterial: {}", self.path, skipped.join(", "));
 			}
-			)
+		}
+
+		ColorMaterial {
+			color: self.color.map(Color::from).unwrap_or(Color::WHITE),
+			texture: self
+				.texture_asset_path("color")
+				.map(|path| loader.load_material_texture(path, self.sampler_of("color"))),
+		}
+	}
+
+	/// Same as [`MaterialToml::load`] but textures missing on disk are replaced with the [`MaterialTomlFallbacks`] for their field,
+	/// logging a warning for each replacement.
+	/// # Panics
+	/// If the path field is `None` or has no parent.
+	pub fn load_with_fallbacks(&self, asset_server: &AssetServer, fallbacks: &MaterialTomlFallbacks) -> StandardMaterial {
+		self.load_dir().expect("MaterialToml::load_with_fallbacks requires a path field with a parent");
+
+		self.load_with(&mut FallbackTextureLoader {
+			asset_server,
+			fallbacks,
+			toml_path: self.path.as_deref(),
+		})
+	}
+
+	/// A material toml with every field described and commented out, set to an example value.
+	/// Removing the `#` before a field uncomments it.
+	pub fn template() -> String {
+		let example = Self {
+			alpha_cutoff: Some(0.5),
+			extends: Some(PathBuf::from("materials/base")),
+			extension: Some(MaterialTomlExtension::Fallbacks(vec![String::from("ktx2"), String::from("png")])),
+			extra: Some(toml::Table::from_iter([(String::from("author"), toml::Value::from("name"))])),
+			lightmap_uv_rect: Some([Vec2::ZERO, Vec2::ONE]),
+
+			variants: Some(BTreeMap::from([(
+				String::from("wet"),
+				Self {
+					rough: Some(0.2),
+					..default()
+				},
+			)])),
+
+			..Self::example()
 		};
 
+		let mut table = toml::Value::try_from(example).unwrap_or_else(|_| toml::Table::new().into());
+		shorten_floats(&mut table);
+		let mut template = String::from("# Every field is optional.\n# Textures are loaded from files named after their stem next to this toml, like `color.png` and `normal.png`.\n");
+
+		for (field, description) in TEMPLATE_FIELDS {
+			template.push('\n');
+
+			for line in description.lines() {
+				template.push_str(&format!("# {line}\n"));
+			}
+
+			if let Some(value) = table.get(*field) {
+				template.push_str(&format!("#{field} = {value}\n"));
+			}
+		}
+
+		template
+	}
+
+	/// Writes the [`MaterialToml::template`] to the `path`.
+	/// If pointed to a directory, writes to the `material.toml` file in that directory.
+	pub fn write_template(path: impl Into<PathBuf>) -> Result<(), MaterialTomlError> {
+		let path = toml_file_path(path.into());
+
+		if let Some(dir) = path.parent() {
+			fs::create_dir_all(dir).map_err(|error| MaterialTomlError::Write(dir.to_path_buf(), error))?;
+		}
+
+		fs::write(&path, Self::template()).map_err(|error| MaterialTomlError::Write(path, error))
+	}
+
+	/// Same as [`MaterialToml::load`] but returns [`MaterialTomlError::MissingPath`] instead of panicking.
+	pub fn try_load(&self, asset_server: &AssetServer) -> Result<StandardMaterial, MaterialTomlError> {
+		self.load_dir()?;
+
+		Ok(self.load_with(asset_server))
+	}
+
+	/// The directory textures are loaded from by the [`AssetServer`] methods.
+	/// A relative path without a parent like `material.toml` has an empty directory, which is treated as missing.
+	fn load_dir(&self) -> Result<&Path, MaterialTomlError> {
+		self.dir().filter(|dir| !dir.as_os_str().is_empty()).ok_or(MaterialTomlError::MissingPath)
+	}
+
+	/// Same as [`MaterialToml::load`] but textures are loaded with the `loader`,
+	/// such as the [`LoadContext`] of an [`AssetLoader`].
+	pub fn load_with(&self, mut loader: impl LoadMaterialTomlTexture) -> StandardMaterial {
+		//packed before fn_load borrows the loader
+		let packed_metallic_roughness = self
+			.separate_pbr_paths()
+			.map(|(rough, metal)| loader.load_metallic_roughness(rough, metal, self.sampler_of("combo_0rm")));
+
+		let mut fn_load = |stem: &'static str| self.texture_asset_path(stem).map(|path| loader.load_material_texture(path, self.sampler_of(stem)));
+
 		//create the base material for mutating
 		let mut material = StandardMaterial {
 			base_color_texture: fn_load("color"),
@@ -227,8 +619,21 @@ impl MaterialToml {
 			..default()
 		};
 
-		//ambient occlusion
-		if let Some(true) = self.ao {
+		let pbr_layout = self.pbr_layout.unwrap_or_default();
+
+		//anisotropic specular
+		if let Some(anisotropy) = self.anisotropy {
+			material.anisotropy_rotation = self.anisotropy_rotation.unwrap_or(0.);
+			material.anisotropy_strength = anisotropy;
+
+			#[cfg(feature = "pbr_anisotropy_texture")]
+			{
+				material.anisotropy_texture = fn_load("anisotropy");
+			}
+		}
+
+		//ambient occlusion, unless it comes from the orm texture
+		if let (Some(true), false) = (self.ao, pbr_layout == MaterialTomlPbrLayout::Orm) {
 			material.occlusion_texture = fn_load("ao");
 		}
 
@@ -259,47 +664,33 @@ impl MaterialToml {
 		}
 
 		//base color
-		if let Some((red, green, blue, alpha_opt)) = self.color {
-			material.base_color = Color::linear_rgba(red, green, blue, alpha_opt.unwrap_or(1.));
+		if let Some(color) = self.color {
+			material.base_color = color.into();
+		}
+
+		//alpha mode
+		material.alpha_mode = match (self.alpha, self.color) {
+			(Some(alpha), _) => alpha.into(),
+			(None, Some(color)) if LinearRgba::from(color).alpha < 1. => AlphaMode::Blend,
+			(None, _) => AlphaMode::Opaque,
+		};
+
+		if let Some(alpha_cutoff) = self.alpha_cutoff {
+			match &mut material.alpha_mode {
+				AlphaMode::Mask(cutoff) => *cutoff = alpha_cutoff,
+				alpha_mode => warn!("MaterialToml {:?} has alpha_cutoff but its alpha mode is {alpha_mode:?} instead of mask", self.path),
+			}
+		}
+
+		if let Some(depth_bias) = self.depth_bias {
+			material.depth_bias = depth_bias;
 		}
 
 		//depth via height map
 		if let Some(depth) = self.depth {
-			material.depth_map = if self.depth_hq == Some(true) {
-				//considered hq because of the default sampling
-				fn_load("depth")
-			} else {
-				//load the depth map with nearest-neighbor sampling to save fps
-				fn_asset_path("depth").map(|path|
-				//if we have a non-default ImageSamplerDescriptor,
-				//we need to do some funky stuff to safely send it without degenerating the closure into an FnOnce implementer
-				//mag_filter: ImageFilterMode::Nearest,
-				//min_filter: ImageFilterMode::Nearest,
-				//mipmap_filter: ImageFilterMode::Nearest,
-				if let Some(ref descriptor_ref) = descriptor_arc {
-					let descriptor_send = Arc::clone(descriptor_ref);
-
-					asset_server.load_with_settings(path, move |settings: &mut ImageLoaderSettings| {
-						let mut descriptor = descriptor_send.as_ref().to_owned();
-						descriptor.mag_filter = ImageFilterMode::Nearest;
-						descriptor.min_filter = ImageFilterMode::Nearest;
-						descriptor.mipmap_filter = ImageFilterMode::Nearest;
-						settings.sampler = ImageSampler::Descriptor(descriptor);
-					})
-				} else {
-					//TODO: the else case here does not properly set the filtering modes for perf!
-					//see above for proper setup!
-					asset_server.load_with_settings(path, move |settings: &mut ImageLoaderSettings| {
-						settings.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor::nearest());
-					})
-				}
-				)
-			};
+			material.depth_map = fn_load("depth");
 
-			material.parallax_mapping_method = match self.depth_method {
-				None => ParallaxMappingMethod::Occlusion,
-				Some(max_layers) => ParallaxMappingMethod::Relief { max_steps: max_layers },
-			};
+			material.parallax_mapping_method = self.depth_method.unwrap_or_default().into();
 
 			material.max_parallax_layer_count = self.depth_layers.unwrap_or(16.);
 			material.parallax_depth_scale = depth;
@@ -310,8 +701,11 @@ impl MaterialToml {
 			(None, None) | (Some(false), _) => {}
 
 			(None | Some(true), color_option) => {
-				if let Some((red, green, blue)) = color_option {
-					material.emissive = LinearRgba::new(red, green, blue, 1.);
+				if let Some(color) = color_option {
+					material.emissive = LinearRgba {
+						alpha: 1.,
+						..color.into()
+					};
 				} else {
 					material.emissive = LinearRgba::WHITE;
 				}
@@ -333,8 +727,18 @@ impl MaterialToml {
 
 			[rough, metal] => {
 				material.metallic = metal.unwrap_or(0.);
-				material.metallic_roughness_texture = fn_load("combo_0rm");
 				material.perceptual_roughness = rough.unwrap_or(1.);
+
+				match pbr_layout {
+					MaterialTomlPbrLayout::Combo0rm => material.metallic_roughness_texture = fn_load("combo_0rm"),
+
+					MaterialTomlPbrLayout::Orm => {
+						material.metallic_roughness_texture = fn_load("orm");
+						material.occlusion_texture = material.metallic_roughness_texture.clone();
+					}
+
+					MaterialTomlPbrLayout::Separate => material.metallic_roughness_texture = packed_metallic_roughness,
+				}
 			}
 		}
 
@@ -348,94 +752,2538 @@ impl MaterialToml {
 			}
 		}
 
-		if let Some(uv_offset) = self.uv_scale {
+		//volume
+		if let Some(thickness) = self.thickness {
+			material.thickness = thickness;
+
+			#[cfg(feature = "pbr_transmission_textures")]
+			{
+				material.thickness_texture = fn_load("thickness");
+			}
+		}
+
+		if let Some(attenuation_color) = self.attenuation_color {
+			material.attenuation_color = attenuation_color.into();
+		}
+
+		if let Some(attenuation_distance) = self.attenuation_distance {
+			material.attenuation_distance = attenuation_distance;
+		}
+
+		if let Some(diffuse_transmission) = self.diffuse_transmission {
+			material.diffuse_transmission = diffuse_transmission;
+		}
+
+		if let Some(ior) = self.ior {
+			material.ior = ior;
+		}
+
+		if let Some(uv_offset) = self.uv_offset {
 			material.uv_transform.translation = uv_offset;
 		}
 
-		if let Some(uv_scale) = self.uv_scale {
-			material.uv_transform.matrix2 = Mat2::from_cols(Vec2::X * uv_scale.x, Vec2::Y * uv_scale.y);
+		if self.uv_rotation.is_some() || self.uv_scale.is_some() {
+			material.uv_transform.matrix2 = Mat2::from_scale_angle(self.uv_scale.unwrap_or(Vec2::ONE), self.uv_rotation.unwrap_or(0.));
+		}
+
+		if let Some(unlit) = self.unlit {
+			material.unlit = unlit;
+		}
+
+		if let Some(lightmap_exposure) = self.lightmap_exposure {
+			material.lightmap_exposure = lightmap_exposure;
+		}
+
+		//uv channels
+		let metallic_roughness_stem = match pbr_layout {
+			MaterialTomlPbrLayout::Orm => "orm",
+			MaterialTomlPbrLayout::Combo0rm | MaterialTomlPbrLayout::Separate => "combo_0rm",
+		};
+
+		material.base_color_channel = self.uv_channel_of("color");
+		material.emissive_channel = self.uv_channel_of("emissive");
+		material.metallic_roughness_channel = self.uv_channel_of(metallic_roughness_stem);
+		material.normal_map_channel = self.uv_channel_of("normal");
+
+		material.occlusion_channel = match pbr_layout {
+			MaterialTomlPbrLayout::Orm => self.uv_channel_of("orm"),
+			MaterialTomlPbrLayout::Combo0rm | MaterialTomlPbrLayout::Separate => self.uv_channel_of("ao"),
+		};
+
+		#[cfg(feature = "pbr_anisotropy_texture")]
+		{
+			material.anisotropy_channel = self.uv_channel_of("anisotropy");
+		}
+
+		#[cfg(feature = "pbr_multi_layer_material_textures")]
+		{
+			material.clearcoat_channel = self.uv_channel_of("clearcoat");
+			material.clearcoat_roughness_channel = self.uv_channel_of("clearcoat_rough");
+
+			material.clearcoat_normal_channel = match self.clearcoat_normal {
+				Some(MaterialTomlClearcoatMode::BaseNormal) => self.uv_channel_of("normal"),
+				_ => self.uv_channel_of("clearcoat_normal"),
+			};
+		}
+
+		#[cfg(feature = "pbr_transmission_textures")]
+		{
+			material.specular_transmission_channel = self.uv_channel_of("specular_trans");
+			material.thickness_channel = self.uv_channel_of("thickness");
 		}
 
 		material
 	}
 
 	/// If pointed to a directory, tries to load the `material.toml` file in that directory.
+	/// Also loads the tomls it `extends`.
 	pub fn new(path: impl Into<PathBuf>) -> Result<Self, MaterialTomlError> {
-		let mut path = path.into();
+		let fn_read = |path: PathBuf| -> Result<Self, MaterialTomlError> {
+			let toml = fs::read_to_string(disk_path(&path)).map_err(|error| MaterialTomlError::StdIo(path.clone(), error))?;
 
-		if path.extension().is_none() {
-			path = path.join("material.toml");
-		}
+			//if relative, should not have assets prefixed
+			Self::from_toml_with_path(&toml, path)
+		};
 
-		let mut mat_toml = toml::from_str::<MaterialToml>(&fs::read_to_string(if path.is_relative() {
-			PathBuf::from("assets").join(&path)
-		} else {
-			path.clone()
-		})?)?;
+		let path = toml_file_path(path.into());
+		let mut chain = vec![path.clone()];
+		let mut mat_toml = fn_read(path)?;
+		let mut ancestors = Vec::new();
+		let mut next = mat_toml.extends_path();
 
-		//if relative, should not have assets prefixed
-		mat_toml.path = Some(path);
+		while let Some(parent_path) = next {
+			push_inheritance_chain(&mut chain, parent_path.clone())?;
+
+			let parent = fn_read(parent_path)?;
+			next = parent.extends_path();
+
+			ancestors.push(parent);
+		}
+
+		mat_toml.inherit_all(ancestors);
 
 		Ok(mat_toml)
 	}
 
-	/// Writes a serialized material toml using its path field.
-	pub fn save(&self) -> Result<(), MaterialTomlError> {
-		fs::write(self.toml_path().ok_or(MaterialTomlError::MissingPath)?, toml::to_string(&self)?)?;
+	/// Creates a [`MaterialToml`] which loads a material like the `material`, for saving edits made at runtime.
+	/// Fields loading textures are set if the `material` has the texture,
+	/// though every texture those fields load is requested even if the `material` lacked some.
+	/// Also returns the fields that could not represent the `material`.
+	pub fn from_standard_material(material: &StandardMaterial, path: Option<PathBuf>) -> (Self, Vec<MaterialTomlIssue>) {
+		let mut issues = Vec::new();
+		let fn_issue = |field: &'static str| MaterialTomlIssue {
+			field,
+			kind: MaterialTomlIssueKind::Unrepresentable,
+			path: path.clone(),
+		};
 
-		Ok(())
-	}
-}
+		let mut mat_toml = MaterialToml {
+			color: Some(MaterialTomlColor::Linear(material.base_color.into())),
+			reflectance: Some(material.reflectance),
+			..default()
+		};
 
-#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
-pub enum MaterialTomlClearcoatMode {
-	/// Use a seperate normal map for clearcoat.
-	#[default]
-	CustomNormal,
+		match MaterialTomlAlphaMode::try_from(material.alpha_mode) {
+			Ok(alpha) => mat_toml.alpha = Some(alpha),
+			Err(_) => issues.push(fn_issue("alpha")),
+		}
 
-	/// Use the base texture's normal map.
-	BaseNormal,
-}
+		if material.anisotropy_strength != 0. {
+			mat_toml.anisotropy = Some(material.anisotropy_strength);
+			mat_toml.anisotropy_rotation = Some(material.anisotropy_rotation);
+		}
 
-#[derive(Debug, thiserror::Error)]
-pub enum MaterialTomlError {
-	#[error("missing path and/or dir fields")]
-	MissingPath,
+		if material.occlusion_texture.is_some() {
+			mat_toml.ao = Some(true);
+		}
 
-	#[error("StdIo error")]
-	StdIo(#[from] std::io::Error),
+		if material.clearcoat != 0. {
+			mat_toml.clearcoat = Some(material.clearcoat);
+			mat_toml.clearcoat_rough = Some(material.clearcoat_perceptual_roughness);
 
-	#[error("toml serde(de) error")]
-	TomlDeserialization(#[from] toml::de::Error),
+			#[cfg(feature = "pbr_multi_layer_material_textures")]
+			if material.clearcoat_normal_texture.is_some() {
+				mat_toml.clearcoat_normal = Some(match material.clearcoat_normal_texture == material.normal_map_texture {
+					true => MaterialTomlClearcoatMode::BaseNormal,
+					false => MaterialTomlClearcoatMode::CustomNormal,
+				});
+			}
+		}
 
-	#[error("toml serde(ser) error")]
-	TomlSerialization(#[from] toml::ser::Error),
-}
+		if material.depth_map.is_some() {
+			mat_toml.depth = Some(material.parallax_depth_scale);
+			mat_toml.depth_layers = Some(material.max_parallax_layer_count);
+			mat_toml.depth_method = Some(material.parallax_mapping_method.into());
+		}
 
-#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
-pub enum MaterialTomlNormalsYDir {
-	/// Good to go.
-	#[default]
-	OpenGL,
+		if material.emissive_texture.is_some() || material.emissive != LinearRgba::BLACK {
+			mat_toml.emissive = Some(true);
+			mat_toml.emissive_color = Some(MaterialTomlColor::Linear(material.emissive));
+			mat_toml.emissive_exposure = Some(material.emissive_exposure_weight);
+		}
 
-	/// Needs flipping.
-	DirectX,
-}
+		if material.normal_map_texture.is_some() {
+			mat_toml.normal = Some(match material.flip_normal_map_y {
+				true => MaterialTomlNormalsYDir::DirectX,
+				false => MaterialTomlNormalsYDir::OpenGL,
+			});
+		}
 
-impl MaterialTomlNormalsYDir {
-	pub fn should_flip(self) -> bool {
-		match self {
-			MaterialTomlNormalsYDir::OpenGL => false,
-			MaterialTomlNormalsYDir::DirectX => true,
+		if material.metallic_roughness_texture.is_some() {
+			mat_toml.metal = Some(material.metallic);
+			mat_toml.rough = Some(material.perceptual_roughness);
+
+			if material.metallic_roughness_texture == material.occlusion_texture {
+				mat_toml.ao = None;
+				mat_toml.pbr_layout = Some(MaterialTomlPbrLayout::Orm);
+			}
 		}
-	}
-}
 
-// #[test]
-// fn generate_sample_material_toml() {
-// 	fs::write(
-// 		std::env::current_dir().unwrap().join("sample_material.toml"),
-// 		toml::to_string(&MaterialToml::example()).unwrap(),
-// 	)
-// 	.unwrap();
-// }
+		//rough and metal always load a texture, so values without one can't be written
+		if material.metallic_roughness_texture.is_none() {
+			if material.metallic != 0. {
+				issues.push(fn_issue("metal"));
+			}
+
+			if material.perceptual_roughness != 0.5 {
+				issues.push(fn_issue("rough"));
+			}
+		}
+
+		if material.specular_transmission != 0. {
+			mat_toml.specular_trans = Some(material.specular_transmission);
+		}
+
+		if material.thickness != 0. {
+			mat_toml.thickness = Some(material.thickness);
+		}
+
+		if material.attenuation_color != Color::WHITE {
+			mat_toml.attenuation_color = Some(MaterialTomlColor::Linear(material.attenuation_color.into()));
+		}
+
+		if material.attenuation_distance != f32::INFINITY {
+			mat_toml.attenuation_distance = Some(material.attenuation_distance);
+		}
+
+		if material.diffuse_transmission != 0. {
+			mat_toml.diffuse_transmission = Some(material.diffuse_transmission);
+		}
+
+		if material.ior != 1.5 {
+			mat_toml.ior = Some(material.ior);
+		}
+
+		let metallic_roughness_stem = match mat_toml.pbr_layout {
+			Some(MaterialTomlPbrLayout::Orm) => "orm",
+			_ => "combo_0rm",
+		};
+
+		//only channels other than the default are written, and orm has the occlusion channel
+		let uv_channels: BTreeMap<String, MaterialTomlUvChannel> = [
+			(metallic_roughness_stem, &material.metallic_roughness_channel),
+			("ao", &material.occlusion_channel),
+			("color", &material.base_color_channel),
+			("emissive", &material.emissive_channel),
+			("normal", &material.normal_map_channel),
+		]
+		.into_iter()
+		.filter(|(stem, channel)| **channel != UvChannel::Uv0 && !(*stem == "ao" && metallic_roughness_stem == "orm"))
+		.map(|(stem, channel)| (String::from(stem), channel.clone().into()))
+		.collect();
+
+		if !uv_channels.is_empty() {
+			mat_toml.uv_channels = Some(uv_channels);
+		}
+
+		let uv_transform = material.uv_transform;
+
+		if material.depth_bias != 0. {
+			mat_toml.depth_bias = Some(material.depth_bias);
+		}
+
+		if material.lightmap_exposure != 1. {
+			mat_toml.lightmap_exposure = Some(material.lightmap_exposure);
+		}
+
+		if material.unlit {
+			mat_toml.unlit = Some(true);
+		}
+
+		if uv_transform.translation != Vec2::ZERO {
+			mat_toml.uv_offset = Some(uv_transform.translation);
+		}
+
+		if uv_transform.matrix2 != Mat2::IDENTITY {
+			let scale = Vec2::new(uv_transform.matrix2.x_axis.length(), uv_transform.matrix2.y_axis.length());
+			let rotation = uv_transform.matrix2.x_axis.y.atan2(uv_transform.matrix2.x_axis.x);
+
+			//shearing and mirroring can't be decomposed into scale and rotation
+			match Mat2::from_scale_angle(scale, rotation).abs_diff_eq(uv_transform.matrix2, 1e-4) {
+				true => {
+					mat_toml.uv_rotation = Some(rotation);
+					mat_toml.uv_scale = Some(scale);
+				}
+
+				false => issues.push(fn_issue("uv_scale")),
+			}
+		}
+
+		mat_toml.path = path;
+
+		(mat_toml, issues)
+	}
+
+	/// The `extends` field resolved to a toml file.
+	fn extends_path(&self) -> Option<PathBuf> {
+		self.extends.clone().map(toml_file_path)
+	}
+
+	/// Same as [`MaterialToml::new`] but parses the `toml` string instead of reading a file,
+	/// such as one embedded in the binary or generated by tooling.
+	/// Textures are located in the `base_dir`, which may have an asset source like `embedded://`.
+	/// The `extends` field is not followed.
+	pub fn from_toml_str(toml: &str, base_dir: impl Into<PathBuf>) -> Result<Self, MaterialTomlError> {
+		Self::from_toml_with_path(toml, base_dir.into().join("material.toml"))
+	}
+
+	/// Same as [`MaterialToml::from_toml_str`] but the `path` is of the toml instead of its directory.
+	fn from_toml_with_path(toml: &str, path: PathBuf) -> Result<Self, MaterialTomlError> {
+		let mut mat_toml = toml::from_str::<MaterialToml>(toml).map_err(|error| {
+			let suggestion = suggest_field(error.message());
+
+			MaterialTomlError::TomlDeserialization(path.clone(), Box::new(error), suggestion)
+		})?;
+		mat_toml.path = Some(path);
+
+		Ok(mat_toml)
+	}
+
+	/// Overlays the fields of the `parent` which are unset.
+	fn inherit(&mut self, parent: MaterialToml) {
+		let parent_dir = parent.dir().map(Path::to_path_buf);
+
+		//textures of unset fields are found next to the toml that set them
+		for (field, set) in [
+			("anisotropy", self.anisotropy.is_some()),
+			("ao", self.ao.is_some()),
+			("clearcoat", self.clearcoat.is_some()),
+			("clearcoat_normal", self.clearcoat_normal.is_some()),
+			("clearcoat_rough", self.clearcoat_rough.is_some()),
+			("color", self.color.is_some()),
+			("depth", self.depth.is_some()),
+			("emissive", self.emissive.is_some() || self.emissive_color.is_some()),
+			("lightmap", self.lightmap.is_some()),
+			("normal", self.normal.is_some()),
+			("pbr_layout", self.pbr_layout.is_some() || self.rough.is_some() || self.metal.is_some()),
+			("specular_trans", self.specular_trans.is_some()),
+			("thickness", self.thickness.is_some()),
+		] {
+			if set {
+				continue;
+			}
+
+			if let Some(dir) = parent.inherited_dirs.get(field).cloned().or_else(|| parent_dir.clone()) {
+				self.inherited_dirs.insert(field, dir);
+			}
+		}
+
+		//destructured so new fields can't be forgotten
+		let MaterialToml {
+			alpha,
+			alpha_cutoff,
+			anisotropy,
+			anisotropy_rotation,
+			ao,
+			attenuation_color,
+			attenuation_distance,
+			clearcoat,
+			clearcoat_normal,
+			clearcoat_rough,
+			color,
+			depth,
+			depth_bias,
+			depth_hq,
+			depth_layers,
+			depth_method,
+			diffuse_transmission,
+			emissive,
+			emissive_color,
+			emissive_exposure,
+			extends: _,
+			extension,
+			extra,
+			ior,
+			lightmap,
+			lightmap_exposure,
+			lightmap_uv_rect,
+			metal,
+			rough,
+			normal,
+			pbr_layout,
+			reflectance,
+			sampler,
+			specular_trans,
+			thickness,
+			tile,
+			unlit,
+			uv_channel,
+			uv_channels,
+			uv_offset,
+			uv_rotation,
+			uv_scale,
+			variants,
+			inherited_dirs: _,
+			path: _,
+		} = parent;
+
+		self.alpha = self.alpha.or(alpha);
+		self.alpha_cutoff = self.alpha_cutoff.or(alpha_cutoff);
+		self.anisotropy = self.anisotropy.or(anisotropy);
+		self.anisotropy_rotation = self.anisotropy_rotation.or(anisotropy_rotation);
+		self.ao = self.ao.or(ao);
+		self.attenuation_color = self.attenuation_color.or(attenuation_color);
+		self.attenuation_distance = self.attenuation_distance.or(attenuation_distance);
+		self.clearcoat = self.clearcoat.or(clearcoat);
+		self.clearcoat_normal = self.clearcoat_normal.or(clearcoat_normal);
+		self.clearcoat_rough = self.clearcoat_rough.or(clearcoat_rough);
+		self.color = self.color.or(color);
+		self.depth = self.depth.or(depth);
+		self.depth_bias = self.depth_bias.or(depth_bias);
+		self.depth_hq = self.depth_hq.or(depth_hq);
+		self.depth_layers = self.depth_layers.or(depth_layers);
+		self.depth_method = self.depth_method.or(depth_method);
+		self.diffuse_transmission = self.diffuse_transmission.or(diffuse_transmission);
+		self.emissive = self.emissive.or(emissive);
+		self.emissive_color = self.emissive_color.or(emissive_color);
+		self.emissive_exposure = self.emissive_exposure.or(emissive_exposure);
+		self.extension = self.extension.take().or(extension);
+		self.extra = self.extra.take().or(extra);
+		self.ior = self.ior.or(ior);
+		self.lightmap = self.lightmap.or(lightmap);
+		self.lightmap_exposure = self.lightmap_exposure.or(lightmap_exposure);
+		self.lightmap_uv_rect = self.lightmap_uv_rect.or(lightmap_uv_rect);
+		self.metal = self.metal.or(metal);
+		self.rough = self.rough.or(rough);
+		self.normal = self.normal.or(normal);
+		self.pbr_layout = self.pbr_layout.or(pbr_layout);
+		self.reflectance = self.reflectance.or(reflectance);
+		self.sampler = self.sampler.take().or(sampler);
+		self.specular_trans = self.specular_trans.or(specular_trans);
+		self.thickness = self.thickness.or(thickness);
+		self.tile = self.tile.or(tile);
+		self.unlit = self.unlit.or(unlit);
+		self.uv_channel = self.uv_channel.or(uv_channel);
+		self.uv_channels = self.uv_channels.take().or(uv_channels);
+		self.uv_offset = self.uv_offset.or(uv_offset);
+		self.uv_rotation = self.uv_rotation.or(uv_rotation);
+		self.uv_scale = self.uv_scale.or(uv_scale);
+		self.variants = self.variants.take().or(variants);
+	}
+
+	/// Inherits from the `ancestors`, ordered from the parent to the root.
+	fn inherit_all(&mut self, ancestors: Vec<MaterialToml>) {
+		let parent = ancestors.into_iter().rev().reduce(|grandparent, mut parent| {
+			parent.inherit(grandparent);
+
+			parent
+		});
+
+		if let Some(parent) = parent {
+			self.inherit(parent);
+		}
+	}
+
+	/// Creates the sampler for the texture with the `stem`, or `None` if the default sampler should be used.
+	fn sampler_of(&self, stem: &str) -> Option<ImageSamplerDescriptor> {
+		let mut settings = self.sampler.as_ref().and_then(|sampler| sampler.maps.get(stem).copied()).unwrap_or_default();
+
+		//depth maps use nearest-neighbor sampling to save fps, unless hq
+		if stem == "depth" {
+			let filter = match self.depth_hq {
+				Some(true) => MaterialTomlFilter::Linear,
+				_ => MaterialTomlFilter::Nearest,
+			};
+
+			settings = settings.or(MaterialTomlSamplerSettings {
+				filter: Some(filter),
+				mipmap_filter: Some(filter),
+				..default()
+			});
+		}
+
+		if let Some(sampler) = &self.sampler {
+			settings = settings.or(sampler.base);
+		}
+
+		if self.tile == Some(true) {
+			settings.address_mode = settings.address_mode.or(Some(MaterialTomlAddressMode::Repeat));
+		}
+
+		settings.descriptor()
+	}
+
+	/// The UV channel of the texture with the `stem`.
+	fn uv_channel_of(&self, stem: &str) -> UvChannel {
+		self.uv_channels
+			.as_ref()
+			.and_then(|uv_channels| uv_channels.get(stem))
+			.or(self.uv_channel.as_ref())
+			.copied()
+			.unwrap_or_default()
+			.into()
+	}
+
+	/// Returns the paths of the `rough` and `metal` textures if they should be packed for the `separate` layout.
+	fn separate_pbr_paths(&self) -> Option<(AssetPath<'static>, AssetPath<'static>)> {
+		if self.pbr_layout != Some(MaterialTomlPbrLayout::Separate) || (self.rough.is_none() && self.metal.is_none()) {
+			return None;
+		}
+
+		Some((self.texture_asset_path("rough")?, self.texture_asset_path("metal")?))
+	}
+
+	/// Same as [`MaterialToml::texture_path`] but parsed as an [`AssetPath`],
+	/// so directories like `embedded://my_crate/materials/wood` keep their asset source.
+	pub fn texture_asset_path(&self, stem: &str) -> Option<AssetPath<'static>> {
+		Some(asset_path(&self.texture_path(stem)?))
+	}
+
+	/// Returns the path of the texture with the `stem` in the same directory as the toml,
+	/// or the directory of the toml it was inherited from.
+	pub fn texture_path(&self, stem: &str) -> Option<PathBuf> {
+		let dir = match self.inherited_dirs.get(texture_field(stem)) {
+			Some(dir) => dir.as_path(),
+			None => self.dir()?,
+		};
+
+		let path = dir.join(stem);
+		let candidates = self.extension.as_ref().map(MaterialTomlExtension::candidates).unwrap_or_default();
+
+		//the last candidate is used if none exist so errors still point somewhere sensible
+		let extension = match candidates {
+			[] => "png",
+			[extension] => extension,
+
+			[.., last] => candidates
+				.iter()
+				.find(|extension| disk_path(&path.with_extension(extension)).is_file())
+				.unwrap_or(last),
+		};
+
+		Some(path.with_extension(extension))
+	}
+
+	/// Returns the variant with the `name` with its unset fields inherited from this toml,
+	/// or `None` if there is no such variant.
+	pub fn variant(&self, name: &str) -> Option<MaterialToml> {
+		let mut variant = self.variants.as_ref()?.get(name)?.clone();
+		let mut base = self.clone();
+
+		//variants of variants are not supported
+		base.variants = None;
+		variant.variants = None;
+		variant.path = self.path.clone();
+
+		variant.inherit(base);
+
+		Some(variant)
+	}
+
+	/// The names of the variants in the `[variants]` table.
+	pub fn variant_names(&self) -> impl Iterator<Item = &str> {
+		self.variants.iter().flat_map(|variants| variants.keys().map(String::as_str))
+	}
+
+	/// Writes a serialized material toml using its path field.
+	/// Missing directories are created, and the file is replaced only once it is fully written.
+	pub fn save(&self) -> Result<(), MaterialTomlError> {
+		let path = self.toml_path().ok_or(MaterialTomlError::MissingPath)?;
+		let toml = toml::to_string(&self).map_err(|error| MaterialTomlError::TomlSerialization(path.clone(), error))?;
+
+		if let Some(dir) = path.parent() {
+			fs::create_dir_all(dir).map_err(|error| MaterialTomlError::Write(dir.to_path_buf(), error))?;
+		}
+
+		//write to a sibling first so a crash cannot leave a truncated file
+		let mut temp_name = path.file_name().unwrap_or_default().to_owned();
+		temp_name.push(".tmp");
+		let temp_path = path.with_file_name(temp_name);
+
+		fs::write(&temp_path, toml).map_err(|error| MaterialTomlError::Write(temp_path.clone(), error))?;
+		fs::rename(&temp_path, &path).map_err(|error| MaterialTomlError::Write(path, error))?;
+
+		Ok(())
+	}
+
+	/// Sets the path field then calls [`MaterialToml::save`].
+	/// If pointed to a directory, saves to the `material.toml` file in that directory.
+	pub fn save_to(&mut self, path: impl Into<PathBuf>) -> Result<(), MaterialTomlError> {
+		self.path = Some(toml_file_path(path.into()));
+
+		self.save()
+	}
+
+	/// Checks that every texture the settings would load exists on disk,
+	/// and that no field is ignored because of another field.
+	/// Does not use the [`AssetServer`].
+	pub fn validate(&self) -> Vec<MaterialTomlIssue> {
+		let mut issues = Vec::new();
+		let fn_issue = |field: &'static str, kind: MaterialTomlIssueKind| MaterialTomlIssue {
+			field,
+			kind,
+			path: self.path.clone(),
+		};
+
+		if self.dir().is_none() {
+			issues.push(fn_issue("path", MaterialTomlIssueKind::MissingPath));
+		}
+
+		if self.alpha_cutoff.is_some() && !matches!(self.alpha, Some(MaterialTomlAlphaMode::Mask(_))) {
+			issues.push(fn_issue("alpha_cutoff", MaterialTomlIssueKind::Requires("alpha")));
+		}
+
+		if self.lightmap != Some(true) && self.lightmap_uv_rect.is_some() {
+			issues.push(fn_issue("lightmap_uv_rect", MaterialTomlIssueKind::Requires("lightmap")));
+		}
+
+		if self.anisotropy.is_none() && self.anisotropy_rotation.is_some() {
+			issues.push(fn_issue("anisotropy_rotation", MaterialTomlIssueKind::Requires("anisotropy")));
+		}
+
+		if self.clearcoat.is_none() {
+			for (field, set) in [
+				("clearcoat_normal", self.clearcoat_normal.is_some()),
+				("clearcoat_rough", self.clearcoat_rough.is_some()),
+			] {
+				if set {
+					issues.push(fn_issue(field, MaterialTomlIssueKind::Requires("clearcoat")));
+				}
+			}
+		}
+
+		if self.depth.is_none() {
+			for (field, set) in [
+				("depth_hq", self.depth_hq.is_some()),
+				("depth_layers", self.depth_layers.is_some()),
+				("depth_method", self.depth_method.is_some()),
+			] {
+				if set {
+					issues.push(fn_issue(field, MaterialTomlIssueKind::Requires("depth")));
+				}
+			}
+		}
+
+		if self.emissive == Some(false) {
+			for (field, set) in [
+				("emissive_color", self.emissive_color.is_some()),
+				("emissive_exposure", self.emissive_exposure.is_some()),
+			] {
+				if set {
+					issues.push(fn_issue(field, MaterialTomlIssueKind::Requires("emissive")));
+				}
+			}
+		}
+
+		if self.pbr_layout.is_some() && self.rough.is_none() && self.metal.is_none() {
+			issues.push(fn_issue("pbr_layout", MaterialTomlIssueKind::Requires("rough")));
+		}
+
+		if self.ao == Some(true) && self.pbr_layout == Some(MaterialTomlPbrLayout::Orm) {
+			issues.push(fn_issue("ao", MaterialTomlIssueKind::OverriddenBy("pbr_layout")));
+		}
+
+		//record the textures instead of loading them, so this can't drift from load_with
+		let mut recorder = TexturePathRecorder::default();
+		self.load_with(&mut recorder);
+		self.extras_with(&mut recorder);
+
+		for texture_path in recorder.0.iter().filter(|texture_path| texture_missing(texture_path)) {
+			issues.push(MaterialTomlIssue {
+				field: texture_path_field(texture_path),
+				kind: MaterialTomlIssueKind::MissingTexture,
+				path: Some(disk_path(texture_path.path())),
+			});
+		}
+
+		issues
+	}
+
+	/// Calls [`MaterialToml::validate`] and logs every issue as a warning.
+	/// Returns the issues for further handling.
+	pub fn validate_and_log(&self) -> Vec<MaterialTomlIssue> {
+		let issues = self.validate();
+
+		for issue in &issues {
+			warn!("material toml {:?}: {issue}", self.path);
+		}
+
+		issues
+	}
+}
+
+/// Loads `*.material.toml` files as [`StandardMaterial`] assets.
+/// Texture paths are resolved relative to the directory of the toml file.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MaterialTomlLoader;
+
+impl AssetLoader for MaterialTomlLoader {
+	type Asset = StandardMaterial;
+	type Settings = ();
+	type Error = MaterialTomlError;
+
+	async fn load<'a>(&'a self, reader: &'a mut Reader<'_>, _settings: &'a (), load_context: &'a mut LoadContext<'_>) -> Result<Self::Asset, Self::Error> {
+		//keeps the asset source so textures are loaded from the same one
+		let path = PathBuf::from(load_context.asset_path().without_label().to_string());
+		let mut toml = String::new();
+
+		reader
+			.read_to_string(&mut toml)
+			.await
+			.map_err(|error| MaterialTomlError::StdIo(path.clone(), error))?;
+
+		let mut mat_toml = MaterialToml::from_toml_with_path(&toml, path.clone())?;
+		let mut chain = vec![path];
+		let mut ancestors = Vec::new();
+		let mut next = mat_toml.extends_path();
+
+		//read as dependencies so changing a parent reloads the child
+		while let Some(parent_path) = next {
+			push_inheritance_chain(&mut chain, parent_path.clone())?;
+
+			let bytes = load_context
+				.read_asset_bytes(asset_path(&parent_path))
+				.await
+				.map_err(|error| MaterialTomlError::Read(parent_path.clone(), error.into()))?;
+
+			let parent_toml = String::from_utf8(bytes).map_err(|error| MaterialTomlError::Read(parent_path.clone(), error.into()))?;
+			let parent = MaterialToml::from_toml_with_path(&parent_toml, parent_path)?;
+			next = parent.extends_path();
+
+			ancestors.push(parent);
+		}
+
+		mat_toml.inherit_all(ancestors);
+
+		//packing is async, so it is done before load_with asks for the handle
+		if let Some((rough, metal)) = mat_toml.separate_pbr_paths() {
+			let rough_bytes = load_context
+				.read_asset_bytes(rough.clone())
+				.await
+				.map_err(|error| MaterialTomlError::Read(PathBuf::from(rough.to_string()), error.into()))?;
+
+			let metal_bytes = load_context
+				.read_asset_bytes(metal.clone())
+				.await
+				.map_err(|error| MaterialTomlError::Read(PathBuf::from(metal.to_string()), error.into()))?;
+
+			let packed = pack_metallic_roughness((rough.path(), &rough_bytes), (metal.path(), &metal_bytes), mat_toml.sampler_of("combo_0rm"))?;
+
+			load_context.add_labeled_asset(String::from(METALLIC_ROUGHNESS_LABEL), packed);
+		}
+
+		Ok(mat_toml.load_with(load_context))
+	}
+
+	fn extensions(&self) -> &[&str] {
+		&["material.toml"]
+	}
+}
+
+/// Adds the [`MaterialTomlLoader`] so `asset_server.load::<StandardMaterial>("props/crate/material.toml")` works.
+/// Also adds the [`MaterialTomlCache`].
+pub struct MaterialTomlPlugin;
+
+impl Plugin for MaterialTomlPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_asset_loader::<MaterialTomlLoader>().init_resource::<MaterialTomlCache>();
+	}
+}
+
+/// Adds the [`MaterialTomlRegistry`] and re-applies the tomls of its materials when a [`MaterialTomlReload`] is sent.
+/// Materials loaded through the [`MaterialTomlLoader`] are already hot reloaded by bevy's `file_watcher` feature instead.
+pub struct MaterialTomlHotReloadPlugin {
+	/// How often the registered tomls are checked for changes, never if `None`.
+	poll_interval: Option<Duration>,
+}
+
+impl MaterialTomlHotReloadPlugin {
+	/// Only reloads when a [`MaterialTomlReload`] is sent.
+	pub fn new() -> Self {
+		Self { poll_interval: None }
+	}
+
+	/// Also sends a [`MaterialTomlReload::Path`] for every registered toml modified since the last check.
+	pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+		self.poll_interval = Some(poll_interval);
+
+		self
+	}
+}
+
+impl Default for MaterialTomlHotReloadPlugin {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Plugin for MaterialTomlHotReloadPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<MaterialTomlRegistry>()
+			.add_event::<MaterialTomlReload>()
+			.add_systems(Update, reload_material_tomls);
+
+		if let Some(poll_interval) = self.poll_interval {
+			app.add_systems(
+				Update,
+				(move |mut elapsed: Local<Duration>, registry: Res<MaterialTomlRegistry>, events: EventWriter<MaterialTomlReload>, time: Res<Time<Real>>| {
+					*elapsed += time.delta();
+
+					if *elapsed >= poll_interval {
+						*elapsed = Duration::ZERO;
+
+						poll_material_tomls(registry, events);
+					}
+				})
+				.before(reload_material_tomls),
+			);
+		}
+	}
+}
+
+/// Placeholder images used by [`MaterialToml::load_with_fallbacks`] for textures missing on disk, keyed by field name.
+/// Not added by the [`MaterialTomlPlugin`], so plain loads are never affected.
+#[derive(Clone, Debug, Default, Resource)]
+pub struct MaterialTomlFallbacks {
+	pub textures: HashMap<&'static str, Handle<Image>>,
+}
+
+impl MaterialTomlFallbacks {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the fallback for textures of the `field`, like `normal` or `pbr_layout`.
+	pub fn get(&self, field: &str) -> Option<&Handle<Image>> {
+		self.textures.get(field)
+	}
+
+	/// Sets the fallback for textures of the `field`, returning the previous one.
+	pub fn insert(&mut self, field: &'static str, image: Handle<Image>) -> Option<Handle<Image>> {
+		self.textures.insert(field, image)
+	}
+
+	/// Builder version of [`MaterialTomlFallbacks::insert`].
+	pub fn with(mut self, field: &'static str, image: Handle<Image>) -> Self {
+		self.textures.insert(field, image);
+
+		self
+	}
+}
+
+/// Shares one [`StandardMaterial`] asset between every load of the same toml, so entities using it can be batched.
+/// Materials updated by the [`MaterialTomlHotReloadPlugin`] stay cached as they are changed in place.
+#[derive(Debug, Default, Resource)]
+pub struct MaterialTomlCache {
+	handles: HashMap<PathBuf, Handle<StandardMaterial>>,
+}
+
+impl MaterialTomlCache {
+	/// Forgets every cached material.
+	pub fn clear(&mut self) {
+		self.handles.clear();
+	}
+
+	/// The cached material of the toml at the `path`.
+	pub fn get(&self, path: impl Into<PathBuf>) -> Option<&Handle<StandardMaterial>> {
+		self.handles.get(&cache_key(path.into()))
+	}
+
+	/// Forgets the cached material of the toml at the `path`, so the next load reads the toml again.
+	pub fn invalidate(&mut self, path: impl Into<PathBuf>) -> Option<Handle<StandardMaterial>> {
+		self.handles.remove(&cache_key(path.into()))
+	}
+
+	/// Same as [`LoadStandardMaterial::load_material`] but only the first load of a toml creates a material,
+	/// later loads return the same handle.
+	pub fn load(&mut self, asset_server: &AssetServer, materials: &mut Assets<StandardMaterial>, path: impl Into<PathBuf>) -> Handle<StandardMaterial> {
+		let path = path.into();
+
+		self.handles
+			.entry(cache_key(path.clone()))
+			.or_insert_with(|| materials.add(asset_server.load_material(path)))
+			.clone()
+	}
+}
+
+/// Tracks which toml each [`StandardMaterial`] was loaded from, so the [`MaterialTomlHotReloadPlugin`] can re-apply them.
+#[derive(Debug, Default, Resource)]
+pub struct MaterialTomlRegistry {
+	entries: HashMap<AssetId<StandardMaterial>, (PathBuf, Option<SystemTime>)>,
+}
+
+impl MaterialTomlRegistry {
+	/// Removes all tracked materials.
+	pub fn clear(&mut self) {
+		self.entries.clear();
+	}
+
+	/// Tracks the material as loaded from the toml at the `path`.
+	/// The `path` is the same as the one given to [`MaterialToml::new`].
+	pub fn insert(&mut self, id: impl Into<AssetId<StandardMaterial>>, path: impl Into<PathBuf>) {
+		let path = toml_file_path(path.into());
+		let modified = modified_time(&path);
+
+		self.entries.insert(id.into(), (path, modified));
+	}
+
+	/// Iterates over the tracked materials and their toml paths.
+	pub fn iter(&self) -> impl Iterator<Item = (AssetId<StandardMaterial>, &Path)> {
+		self.entries.iter().map(|(id, (path, _))| (*id, path.as_path()))
+	}
+
+	/// Same as [`LoadStandardMaterial::load_material`] but adds the material to the `materials`
+	/// and tracks it for hot reloading.
+	pub fn load(&mut self, asset_server: &AssetServer, materials: &mut Assets<StandardMaterial>, path: impl Into<PathBuf>) -> Handle<StandardMaterial> {
+		let path = path.into();
+		let handle = materials.add(asset_server.load_material(&path));
+
+		self.insert(&handle, path);
+
+		handle
+	}
+
+	/// The path of the toml the material was loaded from.
+	pub fn path_of(&self, id: impl Into<AssetId<StandardMaterial>>) -> Option<&Path> {
+		self.entries.get(&id.into()).map(|(path, _)| path.as_path())
+	}
+
+	/// Re-reads the toml of the material and replaces the material in place.
+	/// Unchanged textures keep their handles as the [`AssetServer`] returns the already loaded ones.
+	/// Returns `Ok(false)` if the material is not tracked or no longer exists, which also stops tracking it.
+	pub fn reload(
+		&mut self,
+		id: impl Into<AssetId<StandardMaterial>>,
+		asset_server: &AssetServer,
+		materials: &mut Assets<StandardMaterial>,
+	) -> Result<bool, MaterialTomlError> {
+		let id = id.into();
+
+		let Some((path, modified)) = self.entries.get_mut(&id) else {
+			return Ok(false);
+		};
+
+		let Some(material) = materials.get_mut(id) else {
+			self.entries.remove(&id);
+
+			return Ok(false);
+		};
+
+		*modified = modified_time(path);
+		*material = MaterialToml::new(path.clone())?.try_load(asset_server)?;
+
+		Ok(true)
+	}
+
+	/// Stops tracking the material, returning the path of its toml.
+	pub fn remove(&mut self, id: impl Into<AssetId<StandardMaterial>>) -> Option<PathBuf> {
+		self.entries.remove(&id.into()).map(|(path, _)| path)
+	}
+}
+
+/// Makes the [`MaterialTomlHotReloadPlugin`] re-apply tracked tomls.
+#[derive(Clone, Debug, Eq, Event, Hash, PartialEq)]
+pub enum MaterialTomlReload {
+	/// Reload every material in the [`MaterialTomlRegistry`].
+	All,
+
+	/// Reload the materials loaded from the toml at the path.
+	Path(PathBuf),
+}
+
+/// Serialized form of [`AlphaMode`], written as `"blend"` or `{ mask = 0.5 }` in toml.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaterialTomlAlphaMode {
+	#[default]
+	Opaque,
+
+	/// Fully transparent below the cutoff, fully opaque otherwise.
+	Mask(f32),
+
+	Blend,
+	Premultiplied,
+	Add,
+	Multiply,
+}
+
+impl From<MaterialTomlAlphaMode> for AlphaMode {
+	fn from(value: MaterialTomlAlphaMode) -> Self {
+		match value {
+			MaterialTomlAlphaMode::Opaque => AlphaMode::Opaque,
+			MaterialTomlAlphaMode::Mask(cutoff) => AlphaMode::Mask(cutoff),
+			MaterialTomlAlphaMode::Blend => AlphaMode::Blend,
+			MaterialTomlAlphaMode::Premultiplied => AlphaMode::Premultiplied,
+			MaterialTomlAlphaMode::Add => AlphaMode::Add,
+			MaterialTomlAlphaMode::Multiply => AlphaMode::Multiply,
+		}
+	}
+}
+
+impl TryFrom<AlphaMode> for MaterialTomlAlphaMode {
+	type Error = AlphaMode;
+
+	fn try_from(value: AlphaMode) -> Result<Self, Self::Error> {
+		Ok(match value {
+			AlphaMode::Opaque => MaterialTomlAlphaMode::Opaque,
+			AlphaMode::Mask(cutoff) => MaterialTomlAlphaMode::Mask(cutoff),
+			AlphaMode::Blend => MaterialTomlAlphaMode::Blend,
+			AlphaMode::Premultiplied => MaterialTomlAlphaMode::Premultiplied,
+			AlphaMode::Add => MaterialTomlAlphaMode::Add,
+			AlphaMode::Multiply => MaterialTomlAlphaMode::Multiply,
+			unrepresentable => return Err(unrepresentable),
+		})
+	}
+}
+
+/// Serialized form of [`ImageAddressMode`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaterialTomlAddressMode {
+	Repeat,
+	Clamp,
+	Mirror,
+}
+
+impl From<MaterialTomlAddressMode> for ImageAddressMode {
+	fn from(value: MaterialTomlAddressMode) -> Self {
+		match value {
+			MaterialTomlAddressMode::Repeat => ImageAddressMode::Repeat,
+			MaterialTomlAddressMode::Clamp => ImageAddressMode::ClampToEdge,
+			MaterialTomlAddressMode::Mirror => ImageAddressMode::MirrorRepeat,
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum MaterialTomlClearcoatMode {
+	/// Use a seperate normal map for clearcoat.
+	#[default]
+	CustomNormal,
+
+	/// Use the base texture's normal map.
+	BaseNormal,
+}
+
+/// A color written as a hex string like `"#8a5a2b"` or `"#8a5a2bff"`,
+/// an sRGB table like `{ srgb = [0.54, 0.35, 0.17] }`, or a linear array like `[0.25, 0.1, 0.03]`.
+/// The alpha is optional in every form, and the form is kept when serialized.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "MaterialTomlColorForm", into = "MaterialTomlColorForm")]
+pub enum MaterialTomlColor {
+	/// An sRGB hex string.
+	Hex(Srgba),
+
+	/// RGB or RGBA components in linear space.
+	Linear(LinearRgba),
+
+	/// RGB or RGBA components in sRGB space.
+	Srgb(Srgba),
+}
+
+impl From<MaterialTomlColor> for Color {
+	fn from(value: MaterialTomlColor) -> Self {
+		match value {
+			MaterialTomlColor::Hex(srgba) | MaterialTomlColor::Srgb(srgba) => srgba.into(),
+			MaterialTomlColor::Linear(linear) => linear.into(),
+		}
+	}
+}
+
+impl From<MaterialTomlColor> for LinearRgba {
+	fn from(value: MaterialTomlColor) -> Self {
+		Color::from(value).into()
+	}
+}
+
+/// How a [`MaterialTomlColor`] is written in toml.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum MaterialTomlColorForm {
+	Hex(String),
+	Linear(Vec<f32>),
+	Srgb { srgb: Vec<f32> },
+}
+
+impl From<MaterialTomlColor> for MaterialTomlColorForm {
+	fn from(value: MaterialTomlColor) -> Self {
+		//omit the alpha when opaque, like the hex form does
+		let fn_components = |components: [f32; 4]| match components {
+			[red, green, blue, 1.] => vec![red, green, blue],
+			components => components.to_vec(),
+		};
+
+		match value {
+			MaterialTomlColor::Hex(srgba) => Self::Hex(srgba.to_hex()),
+			MaterialTomlColor::Linear(linear) => Self::Linear(fn_components(linear.to_f32_array())),
+			MaterialTomlColor::Srgb(srgba) => Self::Srgb {
+				srgb: fn_components(srgba.to_f32_array()),
+			},
+		}
+	}
+}
+
+impl TryFrom<MaterialTomlColorForm> for MaterialTomlColor {
+	type Error = String;
+
+	fn try_from(value: MaterialTomlColorForm) -> Result<Self, Self::Error> {
+		let fn_components = |components: Vec<f32>| match components[..] {
+			[red, green, blue] => Ok([red, green, blue, 1.]),
+			[red, green, blue, alpha] => Ok([red, green, blue, alpha]),
+			_ => Err(format!("expected 3 or 4 color components, found {}", components.len())),
+		};
+
+		match value {
+			MaterialTomlColorForm::Hex(hex) => Srgba::hex(&hex).map(Self::Hex).map_err(|error| format!("invalid hex color {hex:?}: {error}")),
+			MaterialTomlColorForm::Linear(components) => fn_components(components).map(|[red, green, blue, alpha]| Self::Linear(LinearRgba::new(red, green, blue, alpha))),
+			MaterialTomlColorForm::Srgb { srgb } => fn_components(srgb).map(|[red, green, blue, alpha]| Self::Srgb(Srgba::new(red, green, blue, alpha))),
+		}
+	}
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MaterialTomlError {
+	#[error("failed to decode {:?}", .0)]
+	Decode(PathBuf, #[source] Box<dyn std::error::Error + Send + Sync>),
+
+	#[error("material toml inheritance cycle {:?}", .0)]
+	InheritanceCycle(Vec<PathBuf>),
+
+	#[error("missing path and/or dir fields")]
+	MissingPath,
+
+	#[error("{:?} and {:?} must be the same size to be packed", .0, .1)]
+	PackSize(PathBuf, PathBuf),
+
+	#[error("failed to read {:?}", .0)]
+	Read(PathBuf, #[source] Box<dyn std::error::Error + Send + Sync>),
+
+	#[error("failed to read material toml {:?}", .0)]
+	StdIo(PathBuf, #[source] std::io::Error),
+
+	//toml's Display has the line and column, so it is included instead of being the source
+	#[error(
+		"failed to parse material toml {:?}: {}{}",
+		.0,
+		.1.to_string().trim_end(),
+		.2.as_ref().map(|field| format!("\ndid you mean `{field}`?")).unwrap_or_default()
+	)]
+	TomlDeserialization(PathBuf, Box<toml::de::Error>, Option<String>),
+
+	#[error("failed to serialize material toml for {:?}", .0)]
+	TomlSerialization(PathBuf, #[source] toml::ser::Error),
+
+	#[error("failed to write {:?}", .0)]
+	Write(PathBuf, #[source] std::io::Error),
+}
+
+/// A problem found by [`MaterialToml::validate`] or [`MaterialToml::from_standard_material`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MaterialTomlIssue {
+	/// Name of the field with the problem.
+	pub field: &'static str,
+
+	/// What the problem is.
+	pub kind: MaterialTomlIssueKind,
+
+	/// The texture path that was checked, otherwise the path of the toml.
+	pub path: Option<PathBuf>,
+}
+
+impl Display for MaterialTomlIssue {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self.kind {
+			MaterialTomlIssueKind::MissingPath => write!(f, "`{}` is unset so textures cannot be located", self.field),
+			MaterialTomlIssueKind::MissingTexture => write!(
+				f,
+				"`{}` requests missing texture {:?}",
+				self.field,
+				self.path.as_deref().unwrap_or(Path::new("")),
+			),
+			MaterialTomlIssueKind::OverriddenBy(other) => write!(f, "`{}` is ignored because of `{other}`", self.field),
+			MaterialTomlIssueKind::Requires(other) => write!(f, "`{}` is ignored unless `{other}` is enabled", self.field),
+			MaterialTomlIssueKind::Unrepresentable => write!(f, "`{}` cannot represent the material's value", self.field),
+		}
+	}
+}
+
+/// The kind of a [`MaterialTomlIssue`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MaterialTomlIssueKind {
+	/// The path field is unset or has no parent.
+	MissingPath,
+
+	/// The texture file does not exist.
+	MissingTexture,
+
+	/// The field is ignored because of the value of the named field.
+	OverriddenBy(&'static str),
+
+	/// The field is ignored unless the named field is set and enabled.
+	Requires(&'static str),
+
+	/// The value from [`MaterialToml::from_standard_material`] can't be written in the field.
+	Unrepresentable,
+}
+
+/// Serialized form of [`UvChannel`], written as `0` or `1`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum MaterialTomlUvChannel {
+	#[default]
+	Uv0,
+	Uv1,
+}
+
+impl From<MaterialTomlUvChannel> for u8 {
+	fn from(value: MaterialTomlUvChannel) -> Self {
+		match value {
+			MaterialTomlUvChannel::Uv0 => 0,
+			MaterialTomlUvChannel::Uv1 => 1,
+		}
+	}
+}
+
+impl From<MaterialTomlUvChannel> for UvChannel {
+	fn from(value: MaterialTomlUvChannel) -> Self {
+		match value {
+			MaterialTomlUvChannel::Uv0 => UvChannel::Uv0,
+			MaterialTomlUvChannel::Uv1 => UvChannel::Uv1,
+		}
+	}
+}
+
+impl From<UvChannel> for MaterialTomlUvChannel {
+	fn from(value: UvChannel) -> Self {
+		match value {
+			UvChannel::Uv0 => MaterialTomlUvChannel::Uv0,
+			UvChannel::Uv1 => MaterialTomlUvChannel::Uv1,
+		}
+	}
+}
+
+impl TryFrom<u8> for MaterialTomlUvChannel {
+	type Error = String;
+
+	fn try_from(value: u8) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(MaterialTomlUvChannel::Uv0),
+			1 => Ok(MaterialTomlUvChannel::Uv1),
+			_ => Err(format!("uv channel must be 0 or 1, found {value}")),
+		}
+	}
+}
+
+/// What [`MaterialToml::load_with_extras`] loads besides the [`StandardMaterial`].
+#[derive(Clone, Default)]
+pub struct MaterialTomlExtras {
+	/// Insert on the entity with the material, which needs a mesh with a second UV channel.
+	pub lightmap: Option<Lightmap>,
+}
+
+/// A texture file extension like `"png"`, or a list like `["ktx2", "png"]` tried in order.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MaterialTomlExtension {
+	One(String),
+	Fallbacks(Vec<String>),
+}
+
+impl MaterialTomlExtension {
+	/// The extensions in the order they are tried.
+	pub fn candidates(&self) -> &[String] {
+		match self {
+			MaterialTomlExtension::One(extension) => std::slice::from_ref(extension),
+			MaterialTomlExtension::Fallbacks(extensions) => extensions,
+		}
+	}
+}
+
+/// Serialized form of [`ImageFilterMode`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaterialTomlFilter {
+	Nearest,
+	Linear,
+}
+
+impl From<MaterialTomlFilter> for ImageFilterMode {
+	fn from(value: MaterialTomlFilter) -> Self {
+		match value {
+			MaterialTomlFilter::Nearest => ImageFilterMode::Nearest,
+			MaterialTomlFilter::Linear => ImageFilterMode::Linear,
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum MaterialTomlNormalsYDir {
+	/// Good to go.
+	#[default]
+	OpenGL,
+
+	/// Needs flipping.
+	DirectX,
+}
+
+impl MaterialTomlNormalsYDir {
+	pub fn should_flip(self) -> bool {
+		match self {
+			MaterialTomlNormalsYDir::OpenGL => false,
+			MaterialTomlNormalsYDir::DirectX => true,
+		}
+	}
+}
+
+/// Which textures hold the roughness and metallic channels.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaterialTomlPbrLayout {
+	/// Loads `combo_0rm` with roughness in green and metallic in blue.
+	#[default]
+	#[serde(rename = "combo_0rm")]
+	Combo0rm,
+
+	/// Loads `orm` laid out like `combo_0rm`, with ambient occlusion in red.
+	/// The `ao` field is ignored.
+	Orm,
+
+	/// Loads the grayscale `rough` and `metal` textures and packs them into one texture laid out like `combo_0rm`.
+	/// The packed texture uses the `combo_0rm` sampler settings.
+	Separate,
+}
+
+/// The `[sampler]` table of a [`MaterialToml`].
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MaterialTomlSampler {
+	/// Settings used by every texture.
+	#[serde(flatten)]
+	pub base: MaterialTomlSamplerSettings,
+
+	/// Settings for specific textures, keyed by file stem such as `[sampler.normal]`.
+	/// Fields left unset use the `base` settings.
+	#[serde(flatten)]
+	pub maps: BTreeMap<String, MaterialTomlSamplerSettings>,
+}
+
+/// Settings for building an [`ImageSamplerDescriptor`].
+/// Fields left unset use the default sampler's.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct MaterialTomlSamplerSettings {
+	/// Used for the u, v, and w axes.
+	pub address_mode: Option<MaterialTomlAddressMode>,
+
+	/// Maximum anisotropic filtering.
+	/// Above 1 every filter is made `linear` regardless of the other fields, as anisotropic sampling requires it.
+	pub anisotropy: Option<u16>,
+
+	/// Used for both magnification and minification.
+	pub filter: Option<MaterialTomlFilter>,
+
+	pub mipmap_filter: Option<MaterialTomlFilter>,
+}
+
+impl MaterialTomlSamplerSettings {
+	/// Returns `None` if every field is unset.
+	pub fn descriptor(&self) -> Option<ImageSamplerDescriptor> {
+		if *self == Self::default() {
+			return None;
+		}
+
+		let mut descriptor = ImageSamplerDescriptor::default();
+
+		if let Some(address_mode) = self.address_mode {
+			descriptor.address_mode_u = address_mode.into();
+			descriptor.address_mode_v = address_mode.into();
+			descriptor.address_mode_w = address_mode.into();
+		}
+
+		if let Some(anisotropy) = self.anisotropy {
+			descriptor.anisotropy_clamp = anisotropy;
+		}
+
+		if let Some(filter) = self.filter {
+			descriptor.mag_filter = filter.into();
+			descriptor.min_filter = filter.into();
+		}
+
+		if let Some(mipmap_filter) = self.mipmap_filter {
+			descriptor.mipmap_filter = mipmap_filter.into();
+		}
+
+		//wgpu rejects anisotropic samplers with any nearest filter
+		if descriptor.anisotropy_clamp > 1 {
+			descriptor.mag_filter = ImageFilterMode::Linear;
+			descriptor.min_filter = ImageFilterMode::Linear;
+			descriptor.mipmap_filter = ImageFilterMode::Linear;
+		}
+
+		Some(descriptor)
+	}
+
+	/// Fills the unset fields with the fields of `other`.
+	pub fn or(self, other: Self) -> Self {
+		Self {
+			address_mode: self.address_mode.or(other.address_mode),
+			anisotropy: self.anisotropy.or(other.anisotropy),
+			filter: self.filter.or(other.filter),
+			mipmap_filter: self.mipmap_filter.or(other.mipmap_filter),
+		}
+	}
+}
+
+/// Serialized form of [`ParallaxMappingMethod`], written as `"occlusion"` or `{ relief = { max_steps = 8 } }` in toml.
+/// Also deserializes from a bare integer as the max steps of `Relief`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaterialTomlParallaxMethod {
+	#[default]
+	Occlusion,
+
+	Relief {
+		max_steps: u32,
+	},
+}
+
+impl<'de> Deserialize<'de> for MaterialTomlParallaxMethod {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		//same as MaterialTomlParallaxMethod, for deriving Deserialize
+		#[derive(Deserialize)]
+		#[serde(rename_all = "snake_case")]
+		enum Method {
+			Occlusion,
+			Relief { max_steps: u32 },
+		}
+
+		#[derive(Deserialize)]
+		#[serde(untagged)]
+		enum Form {
+			MaxSteps(u32),
+			Method(Method),
+		}
+
+		Ok(match Form::deserialize(deserializer)? {
+			Form::MaxSteps(max_steps) | Form::Method(Method::Relief { max_steps }) => Self::Relief { max_steps },
+			Form::Method(Method::Occlusion) => Self::Occlusion,
+		})
+	}
+}
+
+impl From<ParallaxMappingMethod> for MaterialTomlParallaxMethod {
+	fn from(value: ParallaxMappingMethod) -> Self {
+		match value {
+			ParallaxMappingMethod::Occlusion => MaterialTomlParallaxMethod::Occlusion,
+			ParallaxMappingMethod::Relief { max_steps } => MaterialTomlParallaxMethod::Relief { max_steps },
+		}
+	}
+}
+
+impl From<MaterialTomlParallaxMethod> for ParallaxMappingMethod {
+	fn from(value: MaterialTomlParallaxMethod) -> Self {
+		match value {
+			MaterialTomlParallaxMethod::Occlusion => ParallaxMappingMethod::Occlusion,
+			MaterialTomlParallaxMethod::Relief { max_steps } => ParallaxMappingMethod::Relief { max_steps },
+		}
+	}
+}
+
+/// Packs the grayscale `rough` and `metal` images into the green and blue channels of one image.
+fn pack_metallic_roughness(rough: (&Path, &[u8]), metal: (&Path, &[u8]), sampler: Option<ImageSamplerDescriptor>) -> Result<Image, MaterialTomlError> {
+	let decode = |(path, bytes): (&Path, &[u8])| -> Result<_, MaterialTomlError> {
+		let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or("png");
+		let decode_error = |error: Box<dyn std::error::Error + Send + Sync>| MaterialTomlError::Decode(path.to_path_buf(), error);
+
+		let image = Image::from_buffer(
+			bytes,
+			ImageType::Extension(extension),
+			CompressedImageFormats::NONE,
+			false,
+			ImageSampler::Default,
+			RenderAssetUsages::default(),
+		)
+		.map_err(|error| decode_error(error.into()))?;
+
+		Ok(image.try_into_dynamic().map_err(|error| decode_error(error.into()))?.to_luma8())
+	};
+
+	let rough_luma = decode(rough)?;
+	let metal_luma = decode(metal)?;
+
+	if rough_luma.dimensions() != metal_luma.dimensions() {
+		return Err(MaterialTomlError::PackSize(rough.0.to_path_buf(), metal.0.to_path_buf()));
+	}
+
+	let (width, height) = rough_luma.dimensions();
+
+	let mut image = Image::new(
+		Extent3d {
+			width,
+			height,
+			depth_or_array_layers: 1,
+		},
+		TextureDimension::D2,
+		rough_luma
+			.as_raw()
+			.iter()
+			.zip(metal_luma.as_raw())
+			.flat_map(|(rough, metal)| [0, *rough, *metal, u8::MAX])
+			.collect(),
+		TextureFormat::Rgba8Unorm,
+		RenderAssetUsages::default(),
+	);
+
+	if let Some(sampler) = sampler {
+		image.sampler = ImageSampler::Descriptor(sampler);
+	}
+
+	Ok(image)
+}
+
+/// Adds the `path` to the `chain` of inherited tomls, or errors if it is already in it.
+fn push_inheritance_chain(chain: &mut Vec<PathBuf>, path: PathBuf) -> Result<(), MaterialTomlError> {
+	let cycle = chain.contains(&path);
+
+	chain.push(path);
+
+	match cycle {
+		true => Err(MaterialTomlError::InheritanceCycle(chain.clone())),
+		false => Ok(()),
+	}
+}
+
+/// Finds the expected field closest to the unknown one in a serde unknown field `message`.
+fn suggest_field(message: &str) -> Option<String> {
+	//formatted like "unknown field `rougness`, expected one of `rough`, `metal`"
+	if !message.starts_with("unknown field") {
+		return None;
+	}
+
+	let mut names = message.split('`').skip(1).step_by(2);
+	let unknown = names.next()?;
+
+	//close in spelling, or sharing a long enough prefix like `rougness` and `rough`
+	names
+		.map(|name| (levenshtein_distance(unknown, name), name))
+		.filter(|(distance, name)| {
+			let shared_prefix = unknown.chars().zip(name.chars()).take_while(|(a, b)| a == b).count();
+
+			*distance <= (name.len() / 3).max(2) || shared_prefix >= 4
+		})
+		.min_by_key(|(distance, _)| *distance)
+		.map(|(_, name)| String::from(name))
+}
+
+/// Returns the `material.toml` file in the `path` if it is a directory.
+fn toml_file_path(path: PathBuf) -> PathBuf {
+	match path.extension() {
+		Some(_) => path,
+		None => path.join("material.toml"),
+	}
+}
+
+/// Records the texture paths requested by [`MaterialToml::load_with`] instead of loading them.
+#[derive(Default)]
+struct TexturePathRecorder(Vec<AssetPath<'static>>);
+
+impl LoadMaterialTomlTexture for &mut TexturePathRecorder {
+	fn load_metallic_roughness(&mut self, rough: AssetPath<'static>, metal: AssetPath<'static>, _sampler: Option<ImageSamplerDescriptor>) -> Handle<Image> {
+		self.0.push(rough);
+		self.0.push(metal);
+
+		Handle::default()
+	}
+
+	fn load_material_texture(&mut self, path: AssetPath<'static>, _sampler: Option<ImageSamplerDescriptor>) -> Handle<Image> {
+		self.0.push(path);
+
+		Handle::default()
+	}
+}
+
+/// Loads textures with the [`AssetServer`], using [`MaterialTomlFallbacks`] for the missing ones.
+struct FallbackTextureLoader<'a> {
+	asset_server: &'a AssetServer,
+	fallbacks: &'a MaterialTomlFallbacks,
+	toml_path: Option<&'a Path>,
+}
+
+impl FallbackTextureLoader<'_> {
+	/// Returns the fallback for the first missing path that has one, logging a warning.
+	fn fallback(&self, paths: &[&AssetPath<'static>]) -> Option<Handle<Image>> {
+		paths.iter().filter(|path| texture_missing(path)).find_map(|path| {
+			let field = texture_path_field(path);
+			let fallback = self.fallbacks.get(field)?;
+
+			warn!("material toml {:?} is missing {path} for {field}, using the fallback", self.toml_path);
+
+			Some(fallback.clone())
+		})
+	}
+}
+
+impl LoadMaterialTomlTexture for &mut FallbackTextureLoader<'_> {
+	fn load_metallic_roughness(&mut self, rough: AssetPath<'static>, metal: AssetPath<'static>, sampler: Option<ImageSamplerDescriptor>) -> Handle<Image> {
+		match self.fallback(&[&rough, &metal]) {
+			Some(fallback) => fallback,
+			None => self.asset_server.load_metallic_roughness(rough, metal, sampler),
+		}
+	}
+
+	fn load_material_texture(&mut self, path: AssetPath<'static>, sampler: Option<ImageSamplerDescriptor>) -> Handle<Image> {
+		match self.fallback(&[&path]) {
+			Some(fallback) => fallback,
+			None => self.asset_server.load_material_texture(path, sampler),
+		}
+	}
+}
+
+/// If the texture at the `path` doesn't exist on disk.
+/// Textures in other asset sources can't be checked, so they are never missing.
+fn texture_missing(path: &AssetPath) -> bool {
+	*path.source() == AssetSourceId::Default && !disk_path(path.path()).is_file()
+}
+
+/// Same as [`texture_field`] but for the file stem of the texture `path`.
+fn texture_path_field(path: &AssetPath) -> &'static str {
+	texture_field(path.path().file_stem().and_then(|stem| stem.to_str()).unwrap_or_default())
+}
+
+/// Name of the [`MaterialToml`] field that loads the texture with the `stem`.
+fn texture_field(stem: &str) -> &'static str {
+	match stem {
+		"anisotropy" => "anisotropy",
+		"ao" => "ao",
+		"clearcoat" => "clearcoat",
+		"clearcoat_normal" => "clearcoat_normal",
+		"clearcoat_rough" => "clearcoat_rough",
+		"color" => "color",
+		"depth" => "depth",
+		"emissive" => "emissive",
+		"lightmap" => "lightmap",
+		"normal" => "normal",
+		"specular_trans" => "specular_trans",
+		"thickness" => "thickness",
+
+		//combo_0rm, orm, rough, and metal
+		_ => "pbr_layout",
+	}
+}
+
+/// Rounds every float in the `value` to the shortest decimal of its `f32`, so `0.2_f32` is written as `0.2`.
+fn shorten_floats(value: &mut toml::Value) {
+	match value {
+		toml::Value::Float(float) => *float = (*float as f32).to_string().parse().unwrap_or(*float),
+		toml::Value::Array(array) => array.iter_mut().for_each(shorten_floats),
+		toml::Value::Table(table) => table.iter_mut().for_each(|(_, value)| shorten_floats(value)),
+		_ => {}
+	}
+}
+
+/// Canonicalizes the toml `path` so different spellings of it share a [`MaterialTomlCache`] entry.
+fn cache_key(path: PathBuf) -> PathBuf {
+	let path = toml_file_path(path);
+
+	disk_path(&path).canonicalize().unwrap_or(path)
+}
+
+/// Prefixes the `path` with `assets` unless it is absolute.
+fn disk_path(path: &Path) -> PathBuf {
+	match path.is_relative() {
+		true => PathBuf::from("assets").join(path),
+		false => path.to_path_buf(),
+	}
+}
+
+/// The modified time of the toml at the `path`, which is relative to `assets` unless absolute.
+fn modified_time(path: &Path) -> Option<SystemTime> {
+	fs::metadata(disk_path(path)).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// Sends a [`MaterialTomlReload::Path`] for each tracked toml modified since it was last read.
+fn poll_material_tomls(registry: Res<MaterialTomlRegistry>, mut events: EventWriter<MaterialTomlReload>) {
+	let mut modified_paths: Vec<&PathBuf> = registry
+		.entries
+		.values()
+		.filter(|(path, modified)| modified_time(path) != *modified)
+		.map(|(path, _)| path)
+		.collect();
+
+	//multiple materials can share a toml
+	modified_paths.sort();
+	modified_paths.dedup();
+
+	events.send_batch(modified_paths.into_iter().cloned().map(MaterialTomlReload::Path));
+}
+
+/// Re-applies the tomls requested by [`MaterialTomlReload`] events.
+fn reload_material_tomls(
+	asset_server: Res<AssetServer>,
+	mut events: EventReader<MaterialTomlReload>,
+	mut materials: ResMut<Assets<StandardMaterial>>,
+	mut registry: ResMut<MaterialTomlRegistry>,
+) {
+	let mut ids: Vec<AssetId<StandardMaterial>> = Vec::new();
+
+	for event in events.read() {
+		match event {
+			MaterialTomlReload::All => ids.extend(registry.entries.keys().copied()),
+			MaterialTomlReload::Path(path) => ids.extend(registry.iter().filter(|(_, other)| other == path).map(|(id, _)| id)),
+		}
+	}
+
+	ids.sort();
+	ids.dedup();
+
+	for id in ids {
+		if let Err(error) = registry.reload(id, &asset_server, &mut materials) {
+			error!("failed to hot reload material toml: {error}");
+		}
+	}
+}
+
+/// Parses the `path` as an [`AssetPath`], keeping any asset source like `embedded://`.
+fn asset_path(path: &Path) -> AssetPath<'static> {
+	AssetPath::from(path.to_string_lossy().into_owned())
+}
+
+/// Reads the bytes of the file at the `path`.
+async fn read_asset_bytes(asset_server: &AssetServer, path: &AssetPath<'_>) -> Result<Vec<u8>, MaterialTomlError> {
+	let read_error = |error: Box<dyn std::error::Error + Send + Sync>| MaterialTomlError::Read(PathBuf::from(path.to_string()), error);
+	let source = asset_server.get_source(path.source()).map_err(|error| read_error(error.into()))?;
+	let mut reader = source.reader().read(path.path()).await.map_err(|error| read_error(error.into()))?;
+	let mut bytes = Vec::new();
+
+	reader.read_to_end(&mut bytes).await.map_err(|error| read_error(error.into()))?;
+
+	Ok(bytes)
+}
+
+// #[test]
+// fn generate_sample_material_toml() {
+// 	fs::write(
+// 		std::env::current_dir().unwrap().join("sample_material.toml"),
+// 		toml::to_string(&MaterialToml::example()).unwrap(),
+// 	)
+// 	.unwrap();
+// }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn all_linear(descriptor: &ImageSamplerDescriptor) -> bool {
+		[descriptor.mag_filter, descriptor.min_filter, descriptor.mipmap_filter]
+			.into_iter()
+			.all(|filter| matches!(filter, ImageFilterMode::Linear))
+	}
+
+	#[test]
+	fn anisotropy_forces_linear_filters() {
+		let settings = MaterialTomlSamplerSettings {
+			anisotropy: Some(16),
+			filter: Some(MaterialTomlFilter::Nearest),
+			mipmap_filter: Some(MaterialTomlFilter::Nearest),
+			..default()
+		};
+
+		let descriptor = settings.descriptor().unwrap();
+
+		assert_eq!(descriptor.anisotropy_clamp, 16);
+		assert!(all_linear(&descriptor));
+
+		//an anisotropy of 1 is not anisotropic, so the filters are kept
+		let descriptor = MaterialTomlSamplerSettings { anisotropy: Some(1), ..settings }.descriptor().unwrap();
+
+		assert!(matches!(descriptor.mag_filter, ImageFilterMode::Nearest));
+		assert!(matches!(descriptor.mipmap_filter, ImageFilterMode::Nearest));
+	}
+
+	#[test]
+	fn example_samplers_are_valid() {
+		let example = MaterialToml::example();
+		let normal = example.sampler_of("normal").unwrap();
+
+		assert_eq!(normal.anisotropy_clamp, 16);
+		assert!(all_linear(&normal));
+
+		for stem in ["color", "depth", "normal"] {
+			let descriptor = example.sampler_of(stem).unwrap();
+
+			assert!(descriptor.anisotropy_clamp <= 1 || all_linear(&descriptor), "{stem} has an anisotropic sampler with a nearest filter");
+		}
+	}
+
+	/// The paths of every texture the `mat_toml` loads, in the order they are loaded.
+	fn requested_paths(mat_toml: &MaterialToml) -> Vec<String> {
+		let mut recorder = TexturePathRecorder::default();
+		mat_toml.load_with(&mut recorder);
+
+		recorder.0.iter().map(ToString::to_string).collect()
+	}
+
+	#[test]
+	fn pbr_layout_paths() {
+		for (pbr_layout, expected) in [
+			(None, vec!["materials/wood/combo_0rm.png"]),
+			(Some(MaterialTomlPbrLayout::Combo0rm), vec!["materials/wood/combo_0rm.png"]),
+			(Some(MaterialTomlPbrLayout::Orm), vec!["materials/wood/orm.png"]),
+			(Some(MaterialTomlPbrLayout::Separate), vec!["materials/wood/rough.png", "materials/wood/metal.png"]),
+		] {
+			let mat_toml = MaterialToml {
+				ao: Some(true),
+				pbr_layout,
+				rough: Some(0.5),
+				..MaterialToml::from_toml_str("", "materials/wood").unwrap()
+			};
+
+			let mut paths = requested_paths(&mat_toml);
+			paths.retain(|path| !path.ends_with("color.png"));
+
+			//orm replaces the ao texture
+			if pbr_layout != Some(MaterialTomlPbrLayout::Orm) {
+				assert_eq!(paths.iter().filter(|path| path.ends_with("/ao.png")).count(), 1, "{pbr_layout:?}");
+				paths.retain(|path| !path.ends_with("/ao.png"));
+			}
+
+			assert_eq!(paths, expected, "{pbr_layout:?}");
+		}
+	}
+
+	#[test]
+	fn from_standard_material_reports_untextured_metal_and_rough() {
+		let material = StandardMaterial {
+			metallic: 1.,
+			perceptual_roughness: 0.2,
+			..default()
+		};
+
+		let (mat_toml, issues) = MaterialToml::from_standard_material(&material, None);
+		let fields: Vec<&str> = issues.iter().filter(|issue| issue.kind == MaterialTomlIssueKind::Unrepresentable).map(|issue| issue.field).collect();
+
+		assert_eq!(fields, ["metal", "rough"]);
+		assert_eq!((mat_toml.metal, mat_toml.rough), (None, None));
+		assert!(MaterialToml::from_standard_material(&StandardMaterial::default(), None).1.is_empty());
+	}
+
+	#[test]
+	fn from_standard_material_round_trip() {
+		let toml = "color = \"#8a5a2b\"\nrough = 0.75\nmetal = 0.25\nnormal = \"DirectX\"\ndepth = 0.1\ndepth_method = { relief = { max_steps = 4 } }\nuv_scale = [2.0, 2.0]\n";
+		let mat_toml = MaterialToml::from_toml_str(toml, "materials/wood").unwrap();
+		let mut material = mat_toml.load_with(&mut TexturePathRecorder::default());
+
+		//edits made at runtime
+		material.perceptual_roughness = 0.5;
+		material.base_color = Color::srgb(0.25, 0.5, 0.75);
+		material.uv_transform.translation = Vec2::new(0.5, 0.);
+
+		let (exported, issues) = MaterialToml::from_standard_material(&material, mat_toml.path.clone());
+
+		assert!(issues.is_empty(), "{issues:?}");
+
+		let reloaded = MaterialToml::from_toml_str(&toml::to_string(&exported).unwrap(), "materials/wood").unwrap();
+		let reloaded_material = reloaded.load_with(&mut TexturePathRecorder::default());
+
+		assert_eq!(reloaded.rough, Some(0.5));
+		assert_eq!(reloaded.metal, Some(0.25));
+		assert_eq!(reloaded.normal, Some(MaterialTomlNormalsYDir::DirectX));
+		assert_eq!(reloaded.depth_method, Some(MaterialTomlParallaxMethod::Relief { max_steps: 4 }));
+		assert_eq!(reloaded_material.base_color.to_linear(), material.base_color.to_linear());
+		assert_eq!(reloaded_material.perceptual_roughness, material.perceptual_roughness);
+		assert_eq!(reloaded_material.parallax_depth_scale, material.parallax_depth_scale);
+		assert_eq!(reloaded_material.uv_transform, material.uv_transform);
+		assert_eq!(requested_paths(&reloaded), requested_paths(&mat_toml));
+	}
+
+	#[test]
+	fn depth_sampler_of_tile_and_depth_hq() {
+		for tile in [false, true] {
+			for depth_hq in [false, true] {
+				let mat_toml = MaterialToml {
+					depth: Some(0.1),
+					depth_hq: Some(depth_hq),
+					tile: Some(tile),
+					..default()
+				};
+
+				let descriptor = mat_toml.sampler_of("depth").unwrap();
+
+				assert!(
+					[descriptor.address_mode_u, descriptor.address_mode_v, descriptor.address_mode_w]
+						.into_iter()
+						.all(|address_mode| matches!(address_mode, ImageAddressMode::Repeat) == tile),
+					"tile = {tile}, depth_hq = {depth_hq}"
+				);
+
+				assert!(
+					[descriptor.mag_filter, descriptor.min_filter, descriptor.mipmap_filter]
+						.into_iter()
+						.all(|filter| matches!(filter, ImageFilterMode::Linear) == depth_hq),
+					"tile = {tile}, depth_hq = {depth_hq}"
+				);
+			}
+		}
+	}
+
+	/// An empty directory for the test with the `name`, outside of the assets folder.
+	fn temp_dir(name: &str) -> PathBuf {
+		let dir = std::env::temp_dir().join(format!("bevy_cryotheum_{}_{name}", std::process::id()));
+		let _ = fs::remove_dir_all(&dir);
+
+		fs::create_dir_all(&dir).unwrap();
+
+		dir
+	}
+
+	/// Writes the `toml` as the `material.toml` of the `dir`, which is created.
+	fn write_toml(dir: &Path, toml: &str) {
+		fs::create_dir_all(dir).unwrap();
+		fs::write(dir.join("material.toml"), toml).unwrap();
+	}
+
+	#[test]
+	fn extends_two_levels() {
+		let dir = temp_dir("extends_two_levels");
+		let (root, middle, leaf) = (dir.join("root"), dir.join("middle"), dir.join("leaf"));
+
+		write_toml(&root, "color = \"#ff0000\"\nrough = 0.9\nnormal = \"OpenGL\"\n");
+		write_toml(&middle, &format!("extends = {:?}\nrough = 0.4\n", root.to_str().unwrap()));
+		write_toml(&leaf, &format!("extends = {:?}\nmetal = 1.0\n", middle.to_str().unwrap()));
+
+		let mat_toml = MaterialToml::new(&leaf).unwrap();
+
+		assert_eq!(mat_toml.color, Some(MaterialTomlColor::Hex(Srgba::rgb_u8(0xff, 0, 0))));
+		assert_eq!(mat_toml.rough, Some(0.4));
+		assert_eq!(mat_toml.metal, Some(1.));
+
+		//textures are found next to the toml which set their field
+		assert_eq!(mat_toml.texture_path("normal"), Some(root.join("normal.png")));
+		assert_eq!(mat_toml.texture_path("combo_0rm"), Some(leaf.join("combo_0rm.png")));
+
+		fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[test]
+	fn extends_single_field_override() {
+		let dir = temp_dir("extends_single_field_override");
+		let (parent, child) = (dir.join("parent"), dir.join("child"));
+
+		write_toml(&parent, "color = \"#00ff00\"\nreflectance = 0.25\nunlit = true\n");
+		write_toml(&child, &format!("extends = {:?}\nreflectance = 0.75\n", parent.to_str().unwrap()));
+
+		let mat_toml = MaterialToml::new(&child).unwrap();
+
+		assert_eq!(mat_toml.reflectance, Some(0.75));
+		assert_eq!(mat_toml.unlit, Some(true));
+		assert_eq!(mat_toml.color, Some(MaterialTomlColor::Hex(Srgba::rgb_u8(0, 0xff, 0))));
+		assert_eq!(mat_toml.texture_path("color"), Some(parent.join("color.png")));
+
+		fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[test]
+	fn extends_cycle() {
+		let dir = temp_dir("extends_cycle");
+		let (first, second) = (dir.join("first"), dir.join("second"));
+
+		write_toml(&first, &format!("extends = {:?}\n", second.to_str().unwrap()));
+		write_toml(&second, &format!("extends = {:?}\n", first.to_str().unwrap()));
+
+		match MaterialToml::new(&first) {
+			Err(MaterialTomlError::InheritanceCycle(chain)) => assert_eq!(chain.first(), chain.last()),
+			other => panic!("expected an inheritance cycle, found {other:?}"),
+		}
+
+		write_toml(&first, &format!("extends = {:?}\n", first.to_str().unwrap()));
+
+		assert!(matches!(MaterialToml::new(&first), Err(MaterialTomlError::InheritanceCycle(_))));
+
+		fs::remove_dir_all(dir).unwrap();
+	}
+
+	fn parse_color(value: &str) -> Result<MaterialTomlColor, toml::de::Error> {
+		#[derive(Deserialize)]
+		struct Wrapper {
+			color: MaterialTomlColor,
+		}
+
+		toml::from_str::<Wrapper>(&format!("color = {value}")).map(|wrapper| wrapper.color)
+	}
+
+	#[test]
+	fn color_forms() {
+		assert_eq!(parse_color("[0.25, 0.5, 1.0]").unwrap(), MaterialTomlColor::Linear(LinearRgba::new(0.25, 0.5, 1., 1.)));
+		assert_eq!(parse_color("[0.25, 0.5, 1.0, 0.5]").unwrap(), MaterialTomlColor::Linear(LinearRgba::new(0.25, 0.5, 1., 0.5)));
+		assert_eq!(parse_color("{ srgb = [0.25, 0.5, 1.0] }").unwrap(), MaterialTomlColor::Srgb(Srgba::new(0.25, 0.5, 1., 1.)));
+		assert_eq!(parse_color("\"#8a5a2b\"").unwrap(), MaterialTomlColor::Hex(Srgba::rgb_u8(0x8a, 0x5a, 0x2b)));
+		assert_eq!(parse_color("\"#8a5a2b80\"").unwrap(), MaterialTomlColor::Hex(Srgba::rgba_u8(0x8a, 0x5a, 0x2b, 0x80)));
+
+		//srgb and linear differ for the same components
+		assert_ne!(LinearRgba::from(parse_color("{ srgb = [0.5, 0.5, 0.5] }").unwrap()), LinearRgba::rgb(0.5, 0.5, 0.5));
+	}
+
+	#[test]
+	fn color_invalid() {
+		for value in ["\"#8a5a2\"", "\"#zzzzzz\"", "\"8a5a2bff00\"", "[0.5, 0.5]", "{ srgb = [0.5, 0.5, 0.5, 0.5, 0.5] }", "{ rgb = [0.5, 0.5, 0.5] }"] {
+			assert!(parse_color(value).is_err(), "{value} should be invalid");
+		}
+	}
+
+	#[test]
+	fn color_keeps_form_when_serialized() {
+		for value in ["\"#8a5a2b\"", "[0.25, 0.5, 1.0]", "{ srgb = [0.25, 0.5, 1.0, 0.5] }"] {
+			let color = parse_color(value).unwrap();
+			let serialized = toml::Value::try_from(color).unwrap().to_string();
+
+			assert_eq!(parse_color(&serialized).unwrap(), color, "{value} was serialized as {serialized}");
+		}
+	}
+
+	#[test]
+	fn depth_method_forms() {
+		for (value, expected) in [
+			("\"occlusion\"", MaterialTomlParallaxMethod::Occlusion),
+			("{ relief = { max_steps = 8 } }", MaterialTomlParallaxMethod::Relief { max_steps: 8 }),
+			("4", MaterialTomlParallaxMethod::Relief { max_steps: 4 }),
+		] {
+			let mat_toml = MaterialToml::from_toml_str(&format!("depth = 0.1\ndepth_method = {value}"), "materials").unwrap();
+
+			assert_eq!(mat_toml.depth_method, Some(expected), "{value}");
+
+			//the legacy integer is written in the relief form
+			let reparsed = MaterialToml::from_toml_str(&toml::to_string(&mat_toml).unwrap(), "materials").unwrap();
+
+			assert_eq!(reparsed.depth_method, Some(expected), "{value}");
+		}
+
+		assert!(MaterialToml::from_toml_str("depth_method = \"steep\"", "materials").is_err());
+		assert!(MaterialToml::from_toml_str("depth_method = -1", "materials").is_err());
+		assert!(toml::to_string(&MaterialToml::from_toml_str("depth_method = 4", "materials").unwrap()).unwrap().contains("max_steps = 4"));
+	}
+
+	#[test]
+	fn save_to_creates_directories() {
+		let dir = temp_dir("save_to_creates_directories");
+		let material_dir = dir.join("missing").join("wood");
+		let mut mat_toml = MaterialToml::example();
+
+		mat_toml.save_to(&material_dir).unwrap();
+
+		assert_eq!(mat_toml.path, Some(material_dir.join("material.toml")));
+		assert!(material_dir.join("material.toml").is_file());
+		assert!(!material_dir.join("material.toml.tmp").exists());
+
+		fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[test]
+	fn save_round_trip() {
+		let dir = temp_dir("save_round_trip");
+		let mut mat_toml = MaterialToml::example();
+
+		mat_toml.save_to(&dir).unwrap();
+
+		//saving again replaces the file
+		mat_toml.rough = Some(0.25);
+		mat_toml.save().unwrap();
+
+		let loaded = MaterialToml::new(&dir).unwrap();
+
+		assert_eq!(loaded.path, mat_toml.path);
+		assert_eq!(toml::to_string(&loaded).unwrap(), toml::to_string(&mat_toml).unwrap());
+		assert_eq!(loaded.rough, Some(0.25));
+		assert!(matches!(MaterialToml::default().save(), Err(MaterialTomlError::MissingPath)));
+
+		fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[test]
+	fn unknown_field_suggestion() {
+		let error = MaterialToml::from_toml_str("rougness = 0.5", "materials").unwrap_err();
+
+		assert!(matches!(&error, MaterialTomlError::TomlDeserialization(_, _, Some(field)) if field == "rough"));
+		assert_eq!(error.to_string().lines().last(), Some("did you mean `rough`?"));
+
+		//nothing close enough to suggest
+		assert!(matches!(
+			MaterialToml::from_toml_str("zzzzzzzzzzzz = 1", "materials"),
+			Err(MaterialTomlError::TomlDeserialization(_, _, None))
+		));
+	}
+
+	#[test]
+	fn extra_table_passthrough() {
+		let toml = "rough = 0.5\n\n[extra]\nauthor = \"name\"\ntags = [\"wood\", \"old\"]\n\n[extra.editor]\nlocked = true\n";
+		let mat_toml = MaterialToml::from_toml_str(toml, "materials").unwrap();
+		let extra = mat_toml.extra.as_ref().unwrap();
+
+		assert_eq!(extra["author"].as_str(), Some("name"));
+		assert_eq!(extra["editor"]["locked"].as_bool(), Some(true));
+
+		let reparsed = MaterialToml::from_toml_str(&toml::to_string(&mat_toml).unwrap(), "materials").unwrap();
+
+		assert_eq!(reparsed.extra, mat_toml.extra);
+	}
+
+	#[test]
+	fn template_parses_when_uncommented() {
+		let template = MaterialToml::template();
+
+		//descriptions are commented with "# ", fields with only "#"
+		let uncommented: String = template
+			.lines()
+			.map(|line| match line.strip_prefix('#') {
+				Some(field) if !field.starts_with(' ') && !field.is_empty() => format!("{field}\n"),
+				_ => format!("{line}\n"),
+			})
+			.collect();
+
+		assert!(MaterialToml::from_toml_str(&template, "materials").is_ok());
+
+		let mat_toml = MaterialToml::from_toml_str(&uncommented, "materials").unwrap();
+		let fields = toml::Table::try_from(&mat_toml).unwrap();
+
+		//every described field has an example value
+		for (field, _) in TEMPLATE_FIELDS {
+			assert!(fields.contains_key(*field), "{field} is missing from the template");
+		}
+
+		assert_eq!(mat_toml.variant("wet").unwrap().rough, Some(0.2));
+	}
+
+	#[test]
+	fn uv_offset_and_scale_are_separate() {
+		let load = |toml: &str| MaterialToml::from_toml_str(toml, "materials").unwrap().load_with(&mut TexturePathRecorder::default()).uv_transform;
+
+		let offset = load("uv_offset = [0.25, 0.5]");
+
+		assert_eq!(offset.translation, Vec2::new(0.25, 0.5));
+		assert_eq!(offset.matrix2, Mat2::IDENTITY);
+
+		let scale = load("uv_scale = [2.0, 3.0]");
+
+		assert_eq!(scale.translation, Vec2::ZERO);
+		assert_eq!(scale.matrix2, Mat2::from_diagonal(Vec2::new(2., 3.)));
+
+		let both = load("uv_offset = [0.25, 0.5]\nuv_scale = [2.0, 3.0]\nuv_rotation = 1.0");
+
+		assert_eq!(both.translation, Vec2::new(0.25, 0.5));
+		assert!(both.matrix2.abs_diff_eq(Mat2::from_scale_angle(Vec2::new(2., 3.), 1.), 1e-6));
+	}
+
+	#[test]
+	fn variants_overlay_base() {
+		let toml = "color = \"#808080\"\nrough = 0.8\nnormal = \"OpenGL\"\n\n[variants.wet]\nrough = 0.1\n\n[variants.mossy]\ncolor = \"#40ff40\"\nmetal = 0.5\n";
+		let mat_toml = MaterialToml::from_toml_str(toml, "materials/stone").unwrap();
+
+		assert_eq!(mat_toml.variant_names().collect::<Vec<_>>(), ["mossy", "wet"]);
+
+		let wet = mat_toml.variant("wet").unwrap();
+
+		assert_eq!(wet.rough, Some(0.1));
+		assert_eq!(wet.color, mat_toml.color);
+		assert_eq!(wet.normal, Some(MaterialTomlNormalsYDir::OpenGL));
+		assert_eq!(wet.path, mat_toml.path);
+		assert!(wet.variants.is_none());
+
+		let mossy = mat_toml.variant("mossy").unwrap();
+
+		assert_eq!(mossy.rough, Some(0.8));
+		assert_eq!(mossy.metal, Some(0.5));
+		assert_eq!(mossy.color, Some(MaterialTomlColor::Hex(Srgba::rgb_u8(0x40, 0xff, 0x40))));
+		assert_eq!(requested_paths(&mossy), requested_paths(&mat_toml));
+
+		assert!(mat_toml.variant("dry").is_none());
+		assert!(MaterialToml::default().variant("wet").is_none());
+	}
+
+	/// An app with an [`AssetServer`] and the assets used by materials.
+	fn asset_app() -> App {
+		let mut app = App::new();
+
+		app.add_plugins((bevy::MinimalPlugins, bevy::asset::AssetPlugin::default()))
+			.init_asset::<Image>()
+			.init_asset::<StandardMaterial>();
+
+		app
+	}
+
+	#[test]
+	fn cache_shares_one_asset() {
+		use bevy::ecs::world::Mut;
+
+		let dir = temp_dir("cache_shares_one_asset");
+		write_toml(&dir, "color = \"#ff8000\"\n");
+
+		let mut app = asset_app();
+		app.init_resource::<MaterialTomlCache>();
+
+		let world = app.world_mut();
+
+		world.resource_scope(|world, mut cache: Mut<MaterialTomlCache>| {
+			world.resource_scope(|world, mut materials: Mut<Assets<StandardMaterial>>| {
+				let asset_server = world.resource::<AssetServer>();
+				let first = cache.load(asset_server, &mut materials, &dir);
+				let second = cache.load(asset_server, &mut materials, dir.join("material.toml"));
+
+				assert_eq!(first, second);
+				assert_eq!(materials.len(), 1);
+				assert_eq!(cache.get(&dir), Some(&first));
+
+				//invalidating makes the next load create a new asset
+				cache.invalidate(&dir);
+
+				assert_ne!(cache.load(asset_server, &mut materials, &dir), first);
+				assert_eq!(materials.len(), 2);
+			});
+		});
+
+		fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[test]
+	fn hot_reload_changes_material_in_place() {
+		use bevy::ecs::world::Mut;
+
+		let dir = temp_dir("hot_reload_changes_material_in_place");
+		write_toml(&dir, "color = \"#ff0000\"\nnormal = \"OpenGL\"\nrough = 0.9\n");
+
+		let mut app = asset_app();
+		app.add_plugins(MaterialTomlHotReloadPlugin::new());
+
+		let handle = app.world_mut().resource_scope(|world, mut registry: Mut<MaterialTomlRegistry>| {
+			world.resource_scope(|world, mut materials: Mut<Assets<StandardMaterial>>| registry.load(world.resource::<AssetServer>(), &mut materials, &dir))
+		});
+
+		let before = app.world().resource::<Assets<StandardMaterial>>().get(&handle).unwrap().clone();
+
+		assert_eq!(app.world().resource::<MaterialTomlRegistry>().path_of(&handle), Some(dir.join("material.toml").as_path()));
+		assert_eq!(before.perceptual_roughness, 0.9);
+
+		//only the roughness and alpha changed
+		write_toml(&dir, "color = \"#ff000080\"\nnormal = \"OpenGL\"\nrough = 0.2\n");
+		app.world_mut().send_event(MaterialTomlReload::Path(dir.join("material.toml")));
+		app.update();
+
+		let materials = app.world().resource::<Assets<StandardMaterial>>();
+		let after = materials.get(&handle).unwrap();
+
+		assert_eq!(materials.len(), 1);
+		assert_eq!(after.perceptual_roughness, 0.2);
+		assert_eq!(after.alpha_mode, AlphaMode::Blend);
+		assert!(after.normal_map_texture.is_some());
+		assert_eq!(after.normal_map_texture, before.normal_map_texture);
+		assert_eq!(after.metallic_roughness_texture, before.metallic_roughness_texture);
+
+		//materials of other tomls are left alone
+		write_toml(&dir, "rough = 0.5\n");
+		app.world_mut().send_event(MaterialTomlReload::Path(dir.join("other").join("material.toml")));
+		app.update();
+
+		assert_eq!(app.world().resource::<Assets<StandardMaterial>>().get(&handle).unwrap().perceptual_roughness, 0.2);
+
+		app.world_mut().send_event(MaterialTomlReload::All);
+		app.update();
+
+		assert_eq!(app.world().resource::<Assets<StandardMaterial>>().get(&handle).unwrap().perceptual_roughness, 0.5);
+
+		fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[test]
+	fn fallbacks_replace_missing_textures() {
+		let dir = temp_dir("fallbacks_replace_missing_textures");
+		write_toml(&dir, "normal = \"OpenGL\"\n");
+
+		//the color texture exists, the normal map does not
+		fs::write(dir.join("color.png"), []).unwrap();
+
+		let mut app = asset_app();
+		let world = app.world_mut();
+		let (color_fallback, normal_fallback) = {
+			let mut images = world.resource_mut::<Assets<Image>>();
+
+			(images.add(Image::default()), images.add(Image::default()))
+		};
+
+		let fallbacks = MaterialTomlFallbacks::new().with("color", color_fallback.clone()).with("normal", normal_fallback.clone());
+		let mat_toml = MaterialToml::new(&dir).unwrap();
+		let asset_server = world.resource::<AssetServer>();
+		let material = mat_toml.load_with_fallbacks(asset_server, &fallbacks);
+
+		assert_eq!(material.normal_map_texture.as_ref(), Some(&normal_fallback));
+		assert!(material.base_color_texture.is_some());
+		assert_ne!(material.base_color_texture.as_ref(), Some(&color_fallback));
+
+		//plain loads never use the fallbacks
+		let material = mat_toml.load(asset_server);
+
+		assert!(material.normal_map_texture.is_some());
+		assert_ne!(material.normal_map_texture.as_ref(), Some(&normal_fallback));
+
+		fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[test]
+	fn try_load_missing_path() {
+		let app = asset_app();
+		let asset_server = app.world().resource::<AssetServer>();
+
+		for path in [None, Some(PathBuf::new()), Some(PathBuf::from("material.toml"))] {
+			let mat_toml = MaterialToml { path: path.clone(), ..default() };
+
+			assert!(matches!(mat_toml.try_load(asset_server), Err(MaterialTomlError::MissingPath)), "{path:?}");
+			assert!(matches!(mat_toml.try_load_color_material(asset_server), Err(MaterialTomlError::MissingPath)), "{path:?}");
+		}
+
+		//a toml directly in the assets folder has no parent once read
+		let assets = PathBuf::from("assets");
+		let created_assets = !assets.exists();
+		let toml_path = assets.join("material.toml");
+
+		assert!(!toml_path.exists(), "{toml_path:?} would be overwritten");
+		fs::create_dir_all(&assets).unwrap();
+		fs::write(&toml_path, "rough = 0.5\n").unwrap();
+
+		let result = asset_server.try_load_material("material.toml");
+		let color_result = asset_server.try_load_color_material("");
+
+		fs::remove_file(&toml_path).unwrap();
+
+		if created_assets {
+			fs::remove_dir(&assets).unwrap();
+		}
+
+		assert!(matches!(result, Err(MaterialTomlError::MissingPath)));
+		assert!(matches!(color_result, Err(MaterialTomlError::MissingPath)));
+
+		//failed loads return the default material instead
+		assert_eq!(asset_server.load_material("").perceptual_roughness, StandardMaterial::default().perceptual_roughness);
+	}
+
+	#[test]
+	fn texture_path_extension_candidates() {
+		let dir = temp_dir("texture_path_extension_candidates");
+		write_toml(&dir, "extension = [\"ktx2\", \"png\", \"jpg\"]\n");
+
+		//color has both, normal only the second candidate, depth none
+		for file in ["color.ktx2", "color.png", "normal.png"] {
+			fs::write(dir.join(file), []).unwrap();
+		}
+
+		let mat_toml = MaterialToml::new(&dir).unwrap();
+
+		assert_eq!(mat_toml.texture_path("color"), Some(dir.join("color.ktx2")));
+		assert_eq!(mat_toml.texture_path("normal"), Some(dir.join("normal.png")));
+		assert_eq!(mat_toml.texture_path("depth"), Some(dir.join("depth.jpg")));
+
+		//a single extension is used without checking the disk
+		write_toml(&dir, "extension = \"ktx2\"\n");
+
+		assert_eq!(MaterialToml::new(&dir).unwrap().texture_path("normal"), Some(dir.join("normal.ktx2")));
+
+		write_toml(&dir, "");
+
+		assert_eq!(MaterialToml::new(&dir).unwrap().texture_path("color"), Some(dir.join("color.png")));
+
+		fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[test]
+	fn load_with_extras_lightmap() {
+		let app = asset_app();
+		let asset_server = app.world().resource::<AssetServer>();
+		let toml = "lightmap = true\nlightmap_exposure = 250.0\nlightmap_uv_rect = [[0.5, 0.0], [1.0, 0.5]]\n";
+		let (material, extras) = MaterialToml::from_toml_str(toml, "materials/room").unwrap().load_with_extras(asset_server);
+		let lightmap = extras.lightmap.unwrap();
+
+		assert_eq!(lightmap.image.path(), Some(&AssetPath::from("materials/room/lightmap.png")));
+		assert_eq!(lightmap.uv_rect, Rect::from_corners(Vec2::new(0.5, 0.), Vec2::new(1., 0.5)));
+		assert_eq!(material.lightmap_exposure, 250.);
+
+		//the whole lightmap is used without a rect
+		let (_, extras) = MaterialToml::from_toml_str("lightmap = true", "materials/room").unwrap().load_with_extras(asset_server);
+
+		assert_eq!(extras.lightmap.unwrap().uv_rect, Rect::new(0., 0., 1., 1.));
+
+		for (toml, exposure) in [("lightmap = false\nlightmap_exposure = 250.0", 250.), ("", StandardMaterial::default().lightmap_exposure)] {
+			let (material, extras) = MaterialToml::from_toml_str(toml, "materials/room").unwrap().load_with_extras(asset_server);
+
+			assert!(extras.lightmap.is_none(), "{toml}");
+			assert_eq!(material.lightmap_exposure, exposure, "{toml}");
+		}
+	}
+
+	#[test]
+	fn color_material_mapping() {
+		let load = |toml: &str| {
+			let mut recorder = TexturePathRecorder::default();
+			let material = MaterialToml::from_toml_str(toml, "sprites/slime").unwrap().load_color_material_with(&mut recorder);
+			let paths: Vec<String> = recorder.0.iter().map(ToString::to_string).collect();
+
+			(material, paths)
+		};
+
+		let (material, paths) = load("color = \"#40ff4080\"\nextension = \"ktx2\"\ntile = true\n");
+
+		//the alpha of the color is kept, as 2D meshes are always alpha blended
+		assert_eq!(material.color.to_srgba(), Srgba::rgba_u8(0x40, 0xff, 0x40, 0x80));
+		assert!(material.texture.is_some());
+		assert_eq!(paths, ["sprites/slime/color.ktx2"]);
+
+		let (material, _) = load("");
+
+		assert_eq!(material.color, Color::WHITE);
+
+		//ColorMaterial has no alpha mode or uv transform, so those fields are skipped like the PBR ones
+		let (skipped, paths) = load(
+			"color = \"#40ff40\"\nalpha = \"add\"\nuv_offset = [0.5, 0.5]\nuv_scale = [2.0, 2.0]\nrough = 0.5\nmetal = 1.0\nnormal = \"DirectX\"\nemissive = true\ndepth = 0.1\nclearcoat = 1.0\n",
+		);
+
+		assert_eq!(skipped.color.to_srgba(), Srgba::rgb_u8(0x40, 0xff, 0x40));
+		assert_eq!(paths, ["sprites/slime/color.png"]);
+
+		//the example has every kind of field
+		let app = asset_app();
+		let example = MaterialToml {
+			path: Some(PathBuf::from("sprites/slime/material.toml")),
+			..MaterialToml::example()
+		};
+
+		assert!(example.try_load_color_material(app.world().resource::<AssetServer>()).is_ok());
+	}
+
+	#[test]
+	fn uv_channels_reach_material() {
+		let load = |toml: &str| MaterialToml::from_toml_str(toml, "materials").unwrap().load_with(&mut TexturePathRecorder::default());
+
+		let material = load("uv_channel = 1\n\n[uv_channels]\nnormal = 0\nao = 0\n");
+
+		assert_eq!(material.base_color_channel, UvChannel::Uv1);
+		assert_eq!(material.emissive_channel, UvChannel::Uv1);
+		assert_eq!(material.metallic_roughness_channel, UvChannel::Uv1);
+		assert_eq!(material.normal_map_channel, UvChannel::Uv0);
+		assert_eq!(material.occlusion_channel, UvChannel::Uv0);
+
+		#[cfg(feature = "pbr_multi_layer_material_textures")]
+		assert_eq!(material.clearcoat_channel, UvChannel::Uv1);
+
+		#[cfg(feature = "pbr_transmission_textures")]
+		assert_eq!(material.thickness_channel, UvChannel::Uv1);
+
+		//orm textures use the channel of the orm stem for occlusion
+		let material = load("pbr_layout = \"orm\"\n\n[uv_channels]\norm = 1\nao = 0\n");
+
+		assert_eq!(material.metallic_roughness_channel, UvChannel::Uv1);
+		assert_eq!(material.occlusion_channel, UvChannel::Uv1);
+		assert_eq!(material.base_color_channel, UvChannel::Uv0);
+
+		for toml in ["uv_channel = 2", "uv_channel = -1", "uv_channel = \"1\"", "[uv_channels]\ncolor = 2"] {
+			assert!(
+				matches!(MaterialToml::from_toml_str(toml, "materials"), Err(MaterialTomlError::TomlDeserialization(..))),
+				"{toml} should be invalid"
+			);
+		}
+	}
+
+	#[test]
+	fn malformed_file_error_has_path() {
+		let dir = temp_dir("malformed_file_error_has_path");
+		write_toml(&dir, "rough = 0.5\nrougness = 0.5\n");
+
+		let message = MaterialToml::new(&dir).unwrap_err().to_string();
+
+		assert!(message.contains(&format!("{:?}", dir.join("material.toml"))), "{message}");
+		assert!(message.contains("line 2"), "{message}");
+		assert!(message.ends_with("\ndid you mean `rough`?"), "{message}");
+
+		write_toml(&dir, "rough = \n");
+
+		let message = MaterialToml::new(&dir).unwrap_err().to_string();
+
+		assert!(message.contains(&format!("{:?}", dir.join("material.toml"))), "{message}");
+		assert!(!message.contains("did you mean"), "{message}");
+
+		fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[test]
+	fn alpha_mode_forms() {
+		for (value, expected, alpha_mode) in [
+			("\"opaque\"", MaterialTomlAlphaMode::Opaque, AlphaMode::Opaque),
+			("{ mask = 0.5 }", MaterialTomlAlphaMode::Mask(0.5), AlphaMode::Mask(0.5)),
+			("\"blend\"", MaterialTomlAlphaMode::Blend, AlphaMode::Blend),
+			("\"premultiplied\"", MaterialTomlAlphaMode::Premultiplied, AlphaMode::Premultiplied),
+			("\"add\"", MaterialTomlAlphaMode::Add, AlphaMode::Add),
+			("\"multiply\"", MaterialTomlAlphaMode::Multiply, AlphaMode::Multiply),
+		] {
+			let mat_toml = MaterialToml::from_toml_str(&format!("alpha = {value}"), "materials").unwrap();
+
+			assert_eq!(mat_toml.alpha, Some(expected), "{value}");
+			assert_eq!(mat_toml.load_with(&mut TexturePathRecorder::default()).alpha_mode, alpha_mode, "{value}");
+			assert_eq!(MaterialTomlAlphaMode::try_from(alpha_mode), Ok(expected), "{value}");
+
+			let reparsed = MaterialToml::from_toml_str(&toml::to_string(&mat_toml).unwrap(), "materials").unwrap();
+
+			assert_eq!(reparsed.alpha, Some(expected), "{value}");
+		}
+
+		assert!(MaterialToml::from_toml_str("alpha = \"mask\"", "materials").is_err());
+		assert!(MaterialToml::from_toml_str("alpha = \"transparent\"", "materials").is_err());
+		assert!(MaterialTomlAlphaMode::try_from(AlphaMode::AlphaToCoverage).is_err());
+	}
+
+	#[test]
+	fn alpha_mode_defaults_from_color() {
+		let alpha_mode = |toml: &str| MaterialToml::from_toml_str(toml, "materials").unwrap().load_with(&mut TexturePathRecorder::default()).alpha_mode;
+
+		assert_eq!(alpha_mode(""), AlphaMode::Opaque);
+		assert_eq!(alpha_mode("color = \"#ff8000\""), AlphaMode::Opaque);
+		assert_eq!(alpha_mode("color = \"#ff800080\""), AlphaMode::Blend);
+		assert_eq!(alpha_mode("color = [1.0, 0.5, 0.0, 0.99]"), AlphaMode::Blend);
+
+		//an explicit mode wins over the alpha of the color
+		assert_eq!(alpha_mode("color = \"#ff800080\"\nalpha = \"opaque\""), AlphaMode::Opaque);
+		assert_eq!(alpha_mode("color = \"#ff8000\"\nalpha = \"add\""), AlphaMode::Add);
+	}
+
+	#[test]
+	fn alpha_cutoff_only_applies_to_mask() {
+		let alpha_mode = |toml: &str| MaterialToml::from_toml_str(toml, "materials").unwrap().load_with(&mut TexturePathRecorder::default()).alpha_mode;
+
+		assert_eq!(alpha_mode("alpha = { mask = 0.5 }\nalpha_cutoff = 0.25"), AlphaMode::Mask(0.25));
+		assert_eq!(alpha_mode("alpha = { mask = 0.5 }"), AlphaMode::Mask(0.5));
+
+		//ignored with a warning otherwise
+		assert_eq!(alpha_mode("alpha = \"blend\"\nalpha_cutoff = 0.25"), AlphaMode::Blend);
+		assert_eq!(alpha_mode("alpha_cutoff = 0.25"), AlphaMode::Opaque);
+		assert_eq!(alpha_mode("color = \"#ff800080\"\nalpha_cutoff = 0.25"), AlphaMode::Blend);
+	}
+
+	#[test]
+	fn depth_bias_and_unlit_reach_material() {
+		let load = |toml: &str| MaterialToml::from_toml_str(toml, "materials").unwrap().load_with(&mut TexturePathRecorder::default());
+
+		let material = load("depth_bias = 10.0\nunlit = true");
+
+		assert_eq!(material.depth_bias, 10.);
+		assert!(material.unlit);
+
+		let material = load("unlit = false");
+
+		assert_eq!(material.depth_bias, StandardMaterial::default().depth_bias);
+		assert!(!material.unlit);
+
+		//depth_bias does not need parallax mapping
+		assert!(load("depth_bias = -2.5").depth_map.is_none());
+	}
+}
@@ -0,0 +1,186 @@
+use super::{RegistryCollection, RegistryError, RegistryId, RegistryItem, RegistryKey};
+use arrayvec::ArrayVec;
+use bevy::prelude::Resource;
+use bevy::utils::HashMap;
+use std::ops::{Index, IndexMut};
+
+/// Same as [`Registry`](super::Registry) but the items are stored in an `ArrayVec`,
+/// so no more than `CAP` items can be registered.
+/// Lookups are provided by [`RegistryCollection`].
+#[derive(Clone, Debug, Resource)]
+pub struct FixedRegistry<T: RegistryItem, const CAP: usize, K: RegistryKey = RegistryId> {
+	/// Maps to the index of the item in the ArrayVec.
+	/// Every index is always in range of `items`.
+	ids: HashMap<K, usize>,
+
+	/// The registered items.
+	items: ArrayVec<(K, T), CAP>,
+}
+
+impl<T: RegistryItem, const CAP: usize, K: RegistryKey> FixedRegistry<T, CAP, K> {
+	pub fn items(&self) -> &ArrayVec<(K, T), CAP> {
+		&self.items
+	}
+
+	pub fn new() -> Self {
+		Self {
+			ids: HashMap::with_capacity(CAP),
+			items: ArrayVec::new(),
+		}
+	}
+}
+
+impl<T: RegistryItem, const CAP: usize> FixedRegistry<T, CAP> {
+	/// Same as `get` but looks up the id without constructing a `RegistryId`.
+	pub fn get_str(&self, registry_id: &str) -> Option<&T> {
+		let index = *self.ids.get(registry_id)?;
+
+		Some(&self.items[index].1)
+	}
+
+	/// Same as `index_of` but looks up the id without constructing a `RegistryId`.
+	pub fn index_of_str(&self, registry_id: &str) -> Option<usize> {
+		self.ids.get(registry_id).copied()
+	}
+}
+
+impl<T: RegistryItem, const CAP: usize, K: RegistryKey> Default for FixedRegistry<T, CAP, K> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: RegistryItem, const CAP: usize, K: RegistryKey> Index<usize> for FixedRegistry<T, CAP, K> {
+	type Output = T;
+
+	fn index(&self, index: usize) -> &Self::Output {
+		&self.items[index].1
+	}
+}
+
+impl<T: RegistryItem, const CAP: usize, K: RegistryKey> Index<&K> for FixedRegistry<T, CAP, K> {
+	type Output = T;
+
+	fn index(&self, index: &K) -> &Self::Output {
+		&self.items[*self.ids.get(index).expect("failed to index FixedRegistry")].1
+	}
+}
+
+impl<T: RegistryItem, const CAP: usize, K: RegistryKey> IndexMut<usize> for FixedRegistry<T, CAP, K> {
+	fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+		&mut self.items[index].1
+	}
+}
+
+impl<T: RegistryItem, const CAP: usize, K: RegistryKey> IndexMut<&K> for FixedRegistry<T, CAP, K> {
+	fn index_mut(&mut self, index: &K) -> &mut Self::Output {
+		&mut self.items[*self.ids.get(index).expect("failed to index FixedRegistry")].1
+	}
+}
+
+impl<T: RegistryItem, const CAP: usize, K: RegistryKey> RegistryCollection<T, K> for FixedRegistry<T, CAP, K> {
+	fn clear(&mut self) {
+		self.ids.clear();
+		self.items.clear();
+	}
+
+	fn get_indexed_mut(&mut self, index: usize) -> Option<(&K, &mut T)> {
+		self.items.get_mut(index).map(|(registry_id, item)| (&*registry_id, item))
+	}
+
+	fn ids(&self) -> &HashMap<K, usize> {
+		&self.ids
+	}
+
+	/// Fails with [`RegistryError::CapacityExceeded`] if `CAP` items are already registered.
+	fn insert(&mut self, registry_id: impl Into<K>, item: T) -> Result<usize, RegistryError<K>> {
+		let registry_id = registry_id.into();
+
+		if self.ids.contains_key(&registry_id) {
+			return Err(RegistryError::DuplicateId(registry_id));
+		}
+
+		if self.items.is_full() {
+			return Err(RegistryError::CapacityExceeded(CAP));
+		}
+
+		let index = self.items.len();
+
+		self.ids.insert(registry_id.clone(), index);
+		self.items.push((registry_id, item));
+
+		Ok(index)
+	}
+
+	fn items(&self) -> &[(K, T)] {
+		&self.items
+	}
+}
+
+impl<T: RegistryItem, const CAP: usize, K: RegistryKey> IntoIterator for FixedRegistry<T, CAP, K> {
+	type Item = (K, T);
+	type IntoIter = arrayvec::IntoIter<Self::Item, CAP>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.into_iter()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::registry::Registry;
+
+	#[derive(Clone, Debug, PartialEq)]
+	struct Item(u32);
+
+	impl RegistryItem for Item {}
+
+	#[test]
+	fn insert_past_capacity() {
+		let mut registry = FixedRegistry::<Item, 2>::new();
+
+		assert_eq!(registry.insert("test:a", Item(0)).unwrap(), 0);
+		assert!(matches!(registry.insert("test:a", Item(1)), Err(RegistryError::DuplicateId(_))));
+		assert_eq!(registry.insert("test:b", Item(1)).unwrap(), 1);
+		assert!(matches!(registry.insert("test:c", Item(2)), Err(RegistryError::CapacityExceeded(2))));
+
+		//a full registry still reports duplicates first
+		assert!(matches!(registry.insert("test:a", Item(2)), Err(RegistryError::DuplicateId(_))));
+		assert_eq!(registry.len(), 2);
+		assert_eq!(registry.get_str("test:c"), None);
+
+		registry.clear();
+
+		assert!(registry.is_empty());
+		assert_eq!(registry.insert("test:c", Item(2)).unwrap(), 0);
+	}
+
+	/// Runs the same operations on any registry, returning what was observed.
+	fn exercise(registry: &mut impl RegistryCollection<Item, RegistryId>) -> Vec<Option<u32>> {
+		for (index, id) in ["test:b", "test:a", "test:c"].into_iter().enumerate() {
+			registry.insert(id, Item(index as u32)).unwrap();
+		}
+
+		registry.get_mut(RegistryId::from("test:a")).unwrap().0 = 10;
+
+		vec![
+			registry.get(RegistryId::from("test:a")).map(|item| item.0),
+			registry.get(RegistryId::from("test:d")).map(|item| item.0),
+			registry.get_indexed(2).map(|(_, item)| item.0),
+			registry.index_of(RegistryId::from("test:c")).map(|index| index as u32),
+			registry.id_of(0).and_then(|registry_id| registry.index_of(registry_id)).map(|index| index as u32),
+			registry.id_of(3).map(|_| 0),
+			Some(registry.len() as u32),
+		]
+	}
+
+	#[test]
+	fn parity_with_registry() {
+		let mut fixed = FixedRegistry::<Item, 4>::new();
+		let mut registry = Registry::<Item>::new();
+
+		assert_eq!(exercise(&mut fixed), exercise(&mut registry));
+		assert_eq!(fixed.items().as_slice(), registry.items().as_slice());
+	}
+}
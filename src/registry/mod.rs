@@ -0,0 +1,2099 @@
+#[cfg(feature = "arrayvec")]
+pub mod arrayvec;
+
+#[cfg(feature = "arrayvec")]
+pub use crate::registry::arrayvec::*;
+
+use crate::sign::Sign;
+use crate::utils::{sanitize_registry_name, IsAlphaNumeric};
+use anyhow::{anyhow, bail};
+use bevy::app::{App, Plugin, Startup};
+use bevy::ecs::system::SystemParam;
+use bevy::log::error;
+use bevy::prelude::{Event, EventWriter, Local, ReflectResource, Res, ResMut, Resource};
+use bevy::reflect::{FromReflect, Reflect, ReflectFromReflect, TypePath};
+#[cfg(feature = "serde")]
+use bevy::reflect::{ReflectDeserialize, ReflectSerialize};
+use bevy::utils::{HashMap, HashSet};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut, Index, IndexMut};
+use std::str::FromStr;
+use std::sync::{Arc, LazyLock, Mutex, Weak};
+
+pub struct ArcRegistry<T: RegistryItem, K: RegistryKey = RegistryId>(Registry<Arc<T>, K>);
+
+impl<T: RegistryItem, K: RegistryKey> ArcRegistry<T, K> {
+	pub fn get_arc(&self, registry_id: impl Borrow<K>) -> Option<Arc<T>> {
+		self.0.get(registry_id).map(|arc| Arc::clone(arc))
+	}
+
+	pub fn get_ref(&self, registry_id: impl Borrow<K>) -> Option<&T> {
+		match self.0.get(registry_id) {
+			None => None,
+			Some(arc) => Some(arc.as_ref()),
+		}
+	}
+
+	pub fn get_weak(&self, registry_id: impl Borrow<K>) -> Option<Weak<T>> {
+		self.0.get(registry_id).map(|arc| Arc::downgrade(arc))
+	}
+
+	/// Inserts a new RegistryItem into the Registry.
+	pub fn insert(&mut self, registry_id: impl Into<K>, item: T) -> Result<usize, RegistryError<K>> {
+		self.0.insert(registry_id, Arc::new(item))
+	}
+
+	pub fn insert_all(&mut self, registry_ids: impl IntoIterator<Item = (impl Into<K>, T)>) -> Result<(), RegistryErrors<K>> {
+		self.0.insert_all(registry_ids.into_iter().map(|(id, item)| (id, Arc::new(item))))
+	}
+
+	/// Same as [`Registry::try_insert_all`].
+	pub fn try_insert_all(&mut self, registry_ids: impl IntoIterator<Item = (impl Into<K>, T)>) -> Result<(), RegistryErrors<K>> {
+		self.0.try_insert_all(registry_ids.into_iter().map(|(id, item)| (id, Arc::new(item))))
+	}
+
+	pub fn new() -> Self {
+		Self(Registry::new())
+	}
+
+	/// Swaps the `Arc` of a registered item, returning the previous one.
+	/// Existing holders of the previous `Arc` keep the old value, while new lookups see the new one.
+	/// Returns `None` without inserting if the id is not registered.
+	pub fn replace(&mut self, registry_id: impl Borrow<K>, item: T) -> Option<Arc<T>> {
+		let arc = self.0.get_mut(registry_id)?;
+
+		Some(std::mem::replace(arc, Arc::new(item)))
+	}
+
+	/// Same as [`Registry::build_index`] but `key_of` receives the item instead of its `Arc`.
+	pub fn build_index<I: Eq + Hash>(&self, key_of: impl Fn(&T) -> I) -> RegistrySecondaryIndex<I> {
+		self.0.build_index(|arc| key_of(arc))
+	}
+
+	/// Same as [`Registry::get_by`] but yields the items instead of their `Arc`s.
+	pub fn get_by<'a, I: Eq + Hash>(&'a self, index: &'a RegistrySecondaryIndex<I>, key: &I) -> impl Iterator<Item = &'a T> {
+		self.0.get_by(index, key).map(Arc::as_ref)
+	}
+
+	/// Returns the amount of strong references to the item outside of the registry.
+	pub fn strong_count(&self, registry_id: impl Borrow<K>) -> Option<usize> {
+		self.0.get(registry_id).map(|arc| Arc::strong_count(arc) - 1)
+	}
+
+	/// Creates a snapshot of every registered item as a `Weak`.
+	pub fn weak_registry(&self) -> WeakRegistry<T, K> {
+		WeakRegistry {
+			weaks: self.0.items.iter().map(|(registry_id, arc)| (registry_id.clone(), Arc::downgrade(arc))).collect(),
+		}
+	}
+}
+
+impl<T: RegistryItem> ArcRegistry<T> {
+	/// Same as `get_arc` but looks up the id without constructing a `RegistryId`.
+	pub fn get_arc_str(&self, registry_id: &str) -> Option<Arc<T>> {
+		self.0.get_str(registry_id).map(Arc::clone)
+	}
+
+	/// Same as `get_ref` but looks up the id without constructing a `RegistryId`.
+	pub fn get_ref_str(&self, registry_id: &str) -> Option<&T> {
+		self.0.get_str(registry_id).map(Arc::as_ref)
+	}
+
+	/// Same as `get_weak` but looks up the id without constructing a `RegistryId`.
+	pub fn get_weak_str(&self, registry_id: &str) -> Option<Weak<T>> {
+		self.0.get_str(registry_id).map(Arc::downgrade)
+	}
+}
+
+impl<T: RegistryItem, K: RegistryKey, U> AsRef<U> for ArcRegistry<T, K>
+where
+	<ArcRegistry<T, K> as Deref>::Target: AsRef<U>,
+{
+	fn as_ref(&self) -> &U {
+		self.deref().as_ref()
+	}
+}
+
+impl<T: RegistryItem, K: RegistryKey> Default for ArcRegistry<T, K> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: RegistryItem, K: RegistryKey> Deref for ArcRegistry<T, K> {
+	type Target = Registry<Arc<T>, K>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl<T: RegistryItem, K: RegistryKey> DerefMut for ArcRegistry<T, K> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.0
+	}
+}
+
+/// A collection of `T` that contains both keys and indices for each registered item.
+/// Keyed by [`RegistryId`] unless another [`RegistryKey`] is provided as `K`.
+/// Mutating the resource directly does not send any [`RegistryEvent`]s, use [`RegistryWriter`] for that.
+/// Reflected as an opaque value when `T: Clone`.
+#[derive(Clone, Debug, Reflect, Resource)]
+#[reflect_value(Debug, Resource, where T: Clone)]
+pub struct Registry<T: RegistryItem, K: RegistryKey = RegistryId> {
+	/// Maps to the index of the item in the Vec.
+	/// Every index is always in range of `items`.
+	ids: HashMap<K, usize>,
+
+	/// The registered items.
+	items: Vec<(K, T)>,
+
+	/// Maps each tag to the indices of its items, in registry order.
+	tags: HashMap<RegistryId, Vec<usize>>,
+}
+
+impl<T: RegistryItem, K: RegistryKey> Registry<T, K> {
+	/// Maps an additional id to the index of the `target` item.
+	/// Lookups with the alias will resolve to the same item.
+	pub fn alias(&mut self, extra: impl Into<K>, target: impl Borrow<K>) -> Result<(), RegistryError<K>> {
+		let extra = extra.into();
+		let target = target.borrow();
+
+		if self.ids.contains_key(&extra) {
+			return Err(RegistryError::DuplicateId(extra));
+		}
+
+		let index = *self.ids.get(target).ok_or_else(|| RegistryError::MissingId(target.clone()))?;
+
+		self.ids.insert(extra, index);
+
+		Ok(())
+	}
+
+	/// Returns every id which maps to an item registered under a different id, and the index it maps to.
+	pub fn aliases(&self) -> impl Iterator<Item = (&K, usize)> {
+		self.ids
+			.iter()
+			.filter(|(registry_id, index)| self.items[**index].0 != **registry_id)
+			.map(|(registry_id, index)| (registry_id, *index))
+	}
+
+	/// Clears all items and ids from the registry.
+	pub fn clear(&mut self) {
+		self.ids.clear();
+		self.ids.shrink_to(4);
+		self.items.clear();
+		self.items.shrink_to(4);
+		self.tags.clear();
+	}
+
+	/// Reorders the items so their indices match the `table`, such as one made by [`Registry::export_index_table`].
+	/// Items not listed in the table are moved after the listed items, keeping their relative order.
+	/// Fails without changing anything if an id in the table is not registered or listed twice,
+	/// or if the table's indices are not unique and contiguous from zero.
+	pub fn apply_index_table(&mut self, table: impl IntoIterator<Item = (K, u32)>) -> Result<RegistryRemap<K>, RegistryErrors<K>> {
+		let table: Vec<(K, u32)> = table.into_iter().collect();
+		let mut claimed = vec![false; table.len()];
+		let mut errors = Vec::new();
+		let mut permutation = vec![usize::MAX; self.items.len()];
+
+		for (table_index, (registry_id, index)) in table.iter().enumerate() {
+			let new_index = *index as usize;
+
+			if new_index >= table.len() || claimed[new_index] {
+				errors.push((table_index, RegistryError::InvalidIndex(registry_id.clone(), *index)));
+
+				continue;
+			}
+
+			claimed[new_index] = true;
+
+			match self.canonical_index_of(registry_id) {
+				None => errors.push((table_index, RegistryError::MissingId(registry_id.clone()))),
+
+				//the same item listed twice would leave a hole in the permutation
+				Some(old_index) if permutation[old_index] != usize::MAX => errors.push((table_index, RegistryError::DuplicateId(registry_id.clone()))),
+				Some(old_index) => permutation[old_index] = new_index,
+			}
+		}
+
+		RegistryErrors::from_errors(errors)?;
+
+		let mut next_index = table.len();
+		let mut unlisted = Vec::new();
+
+		for (old_index, new_index) in permutation.iter_mut().enumerate() {
+			if *new_index == usize::MAX {
+				*new_index = next_index;
+				next_index += 1;
+
+				unlisted.push(self.items[old_index].0.clone());
+			}
+		}
+
+		self.permute(&permutation);
+
+		Ok(RegistryRemap { permutation, unlisted })
+	}
+
+	/// Groups the indices of all items by the key returned from `key_of`.
+	/// The index must be rebuilt after the registry is mutated.
+	pub fn build_index<I: Eq + Hash>(&self, key_of: impl Fn(&T) -> I) -> RegistrySecondaryIndex<I> {
+		let mut indices: HashMap<I, Vec<usize>> = HashMap::new();
+
+		for (index, (_, item)) in self.items.iter().enumerate() {
+			indices.entry(key_of(item)).or_default().push(index);
+		}
+
+		RegistrySecondaryIndex {
+			indices,
+			len: self.items.len(),
+		}
+	}
+
+	/// Returns the index of the id only if it is not an alias.
+	fn canonical_index_of(&self, registry_id: &K) -> Option<usize> {
+		let index = *self.ids.get(registry_id)?;
+
+		(self.items[index].0 == *registry_id).then_some(index)
+	}
+
+	/// Compares the ids and indices of two registries, ignoring their items.
+	/// Aliases are not compared.
+	pub fn diff<U: RegistryItem>(&self, other: &Registry<U, K>) -> RegistryDiff<K> {
+		let mut diff = RegistryDiff::default();
+
+		for (index, (registry_id, _)) in self.items.iter().enumerate() {
+			match other.canonical_index_of(registry_id) {
+				None => diff.only_in_self.push(registry_id.clone()),
+				Some(other_index) if other_index != index => diff.moved.push((registry_id.clone(), index, other_index)),
+				Some(_) => {}
+			}
+		}
+
+		for (registry_id, _) in &other.items {
+			if self.canonical_index_of(registry_id).is_none() {
+				diff.only_in_other.push(registry_id.clone());
+			}
+		}
+
+		diff
+	}
+
+	/// Lists every registered id with its index, for sending to [`Registry::apply_index_table`].
+	/// Aliases are not included.
+	pub fn export_index_table(&self) -> Result<Vec<(K, u32)>, RegistryError<K>> {
+		self.items
+			.iter()
+			.enumerate()
+			.map(|(index, (registry_id, _))| Ok((registry_id.clone(), u32::try_from(index).map_err(|_| RegistryError::IndexOverflow(index))?)))
+			.collect()
+	}
+
+	/// Returns every item with the key in the `index`, in registry order.
+	/// # Panics
+	/// If the `index` was built when the registry had a different amount of items.
+	pub fn get_by<'a, I: Eq + Hash>(&'a self, index: &'a RegistrySecondaryIndex<I>, key: &I) -> impl Iterator<Item = &'a T> {
+		assert_eq!(
+			index.len,
+			self.items.len(),
+			"RegistrySecondaryIndex is stale, rebuild it after mutating the registry"
+		);
+
+		index.indices(key).iter().map(|index| &self.items[*index].1)
+	}
+
+	/// Returns the index, canonical id, and item of the associated id or alias.
+	pub fn entry_of(&self, registry_id: impl Borrow<K>) -> Option<(usize, &K, &T)> {
+		let index = *self.ids.get(registry_id.borrow())?;
+		let (registry_id, item) = &self.items[index];
+
+		Some((index, registry_id, item))
+	}
+
+	/// Returns a reference to the registry item with the associated id.
+	pub fn get(&self, registry_id: impl Borrow<K>) -> Option<&T> {
+		let index = *self.ids.get(registry_id.borrow())?;
+
+		Some(&self.items[index].1)
+	}
+
+	/// Returns the id and item at the provided index.
+	pub fn get_indexed(&self, index: usize) -> Option<(&K, &T)> {
+		self.items.get(index).map(|(registry_id, item)| (registry_id, item))
+	}
+
+	/// Returns the id and a mutable reference to the item at the provided index.
+	pub fn get_indexed_mut(&mut self, index: usize) -> Option<(&K, &mut T)> {
+		self.items.get_mut(index).map(|(registry_id, item)| (&*registry_id, item))
+	}
+
+	/// Returns a mutable reference to the registry item with the associated id.
+	pub fn get_mut(&mut self, registry_id: impl Borrow<K>) -> Option<&mut T> {
+		let index = *self.ids.get(registry_id.borrow())?;
+
+		Some(&mut self.items[index].1)
+	}
+
+	/// Returns the key of the item at the provided index.
+	pub fn id_of(&self, index: usize) -> Option<&K> {
+		self.items.get(index).map(|(registry_id, _)| registry_id)
+	}
+
+	/// The ids and aliases mapped to the index of their item.
+	pub fn ids(&self) -> &HashMap<K, usize> {
+		&self.ids
+	}
+
+	pub fn items(&self) -> &Vec<(K, T)> {
+		&self.items
+	}
+
+	/// Returns every item with the tag, in registry order.
+	pub fn iter_tag(&self, tag: impl Borrow<RegistryId>) -> impl Iterator<Item = (&K, &T)> {
+		self.tags
+			.get(tag.borrow())
+			.map(Vec::as_slice)
+			.unwrap_or(&[])
+			.iter()
+			.map(|index| {
+				let (registry_id, item) = &self.items[*index];
+
+				(registry_id, item)
+			})
+	}
+
+	/// Returns true if the item with the associated id or alias has the tag.
+	pub fn has_tag(&self, tag: impl Borrow<RegistryId>, registry_id: impl Borrow<K>) -> bool {
+		let (Some(indices), Some(index)) = (self.tags.get(tag.borrow()), self.ids.get(registry_id.borrow())) else {
+			return false;
+		};
+
+		indices.binary_search(index).is_ok()
+	}
+
+	/// Returns the index in the registry at which the associated item is located.
+	pub fn index_of(&self, registry_id: impl Borrow<K>) -> Option<usize> {
+		self.ids.get(registry_id.borrow()).map(|index| *index)
+	}
+
+	/// Inserts a new RegistryItem into the Registry.
+	pub fn insert(&mut self, registry_id: impl Into<K>, item: T) -> Result<usize, RegistryError<K>> {
+		let registry_id = registry_id.into();
+
+		if self.ids.contains_key(&registry_id) {
+			return Err(RegistryError::DuplicateId(registry_id));
+		}
+
+		let index = self.items.len();
+
+		self.ids.insert(registry_id.clone(), index);
+		self.items.push((registry_id, item));
+
+		Ok(index)
+	}
+
+	/// Inserts every item that can be inserted.
+	/// Items that fail are skipped and reported with their index in the input.
+	/// See [`Registry::try_insert_all`] for an all-or-nothing version.
+	pub fn insert_all(&mut self, registry_ids: impl IntoIterator<Item = (impl Into<K>, T)>) -> Result<(), RegistryErrors<K>> {
+		let mut errors = Vec::new();
+
+		for (input_index, (registry_id, item)) in registry_ids.into_iter().enumerate() {
+			if let Err(error) = self.insert(registry_id, item) {
+				errors.push((input_index, error));
+			}
+		}
+
+		RegistryErrors::from_errors(errors)
+	}
+
+	/// Moves the items of `other` into this registry, using `strategy` for ids that are already registered.
+	/// Indices of existing items never change.
+	/// Aliases and tags in `other` are not carried over.
+	pub fn merge(&mut self, other: Registry<T, K>, strategy: MergeStrategy<T, K>) -> Result<MergeReport<K>, RegistryErrors<K>> {
+		let mut report = MergeReport::default();
+
+		if let MergeStrategy::Error = strategy {
+			report.added = other.items.iter().map(|(registry_id, _)| registry_id.clone()).collect();
+
+			self.try_insert_all(other.items)?;
+
+			return Ok(report);
+		}
+
+		for (registry_id, item) in other.items {
+			let Some(&index) = self.ids.get(&registry_id) else {
+				self.ids.insert(registry_id.clone(), self.items.len());
+				self.items.push((registry_id.clone(), item));
+				report.added.push(registry_id);
+
+				continue;
+			};
+
+			match strategy {
+				MergeStrategy::Error => unreachable!(),
+				MergeStrategy::KeepExisting => report.skipped.push(registry_id),
+
+				MergeStrategy::Overwrite => {
+					self.items[index].1 = item;
+					report.overwritten.push(registry_id);
+				}
+
+				MergeStrategy::Resolve(resolve) => {
+					self.items[index].1 = resolve(&registry_id, &self.items[index].1, item);
+					report.overwritten.push(registry_id);
+				}
+			}
+		}
+
+		Ok(report)
+	}
+
+	pub fn new() -> Self {
+		Self {
+			ids: HashMap::new(),
+			items: Vec::new(),
+			tags: HashMap::new(),
+		}
+	}
+
+	/// Creates a table where the value at each index of `other` is the index of the same id in this registry.
+	/// Aliases in this registry are used when resolving ids.
+	/// Returns `None` if any id of `other` is not registered in this registry.
+	pub fn remap_table<U: RegistryItem>(&self, other: &Registry<U, K>) -> Option<Vec<usize>> {
+		other.items.iter().map(|(registry_id, _)| self.index_of(registry_id)).collect()
+	}
+
+	/// Moves each item from its index to the index in `permutation`, and updates the ids to match.
+	fn permute(&mut self, permutation: &[usize]) {
+		let mut slots: Vec<Option<(K, T)>> = (0..self.items.len()).map(|_| None).collect();
+
+		for (old_index, entry) in self.items.drain(..).enumerate() {
+			slots[permutation[old_index]] = Some(entry);
+		}
+
+		self.items = slots.into_iter().map(|slot| slot.expect("permutation must be a bijection")).collect();
+
+		for index in self.ids.values_mut() {
+			*index = permutation[*index];
+		}
+
+		for indices in self.tags.values_mut() {
+			for index in indices.iter_mut() {
+				*index = permutation[*index];
+			}
+
+			indices.sort_unstable();
+		}
+	}
+
+	/// Removes the item with the associated id or alias, returning the index it was at and the item.
+	/// Aliases and tags of the item are removed too.
+	/// Items after it are shifted down, so their indices change.
+	pub fn remove(&mut self, registry_id: impl Borrow<K>) -> Option<(usize, T)> {
+		let index = *self.ids.get(registry_id.borrow())?;
+		let (_, item) = self.items.remove(index);
+
+		self.ids.retain(|_, mapped_index| *mapped_index != index);
+
+		for mapped_index in self.ids.values_mut() {
+			if *mapped_index > index {
+				*mapped_index -= 1;
+			}
+		}
+
+		for indices in self.tags.values_mut() {
+			indices.retain(|tagged_index| *tagged_index != index);
+
+			for tagged_index in indices.iter_mut() {
+				if *tagged_index > index {
+					*tagged_index -= 1;
+				}
+			}
+		}
+
+		self.tags.retain(|_, indices| !indices.is_empty());
+
+		Some((index, item))
+	}
+
+	/// Changes the id of an item without changing its index.
+	/// If `old` is an alias, only the alias is changed.
+	pub fn rename(&mut self, old: impl Borrow<K>, new: impl Into<K>) -> Result<(), RegistryError<K>> {
+		let old = old.borrow();
+		let new = new.into();
+
+		if self.ids.contains_key(&new) {
+			return Err(RegistryError::DuplicateId(new));
+		}
+
+		let index = self.ids.remove(old).ok_or_else(|| RegistryError::MissingId(old.clone()))?;
+		let stored_id = &mut self.items[index].0;
+
+		if stored_id == old {
+			*stored_id = new.clone();
+		}
+
+		self.ids.insert(new, index);
+
+		Ok(())
+	}
+
+	/// Stably sorts the items with `compare`, returning the new index of each previous index.
+	/// Indices held outside the registry are invalidated and must be remapped with the returned table.
+	pub fn sort_by(&mut self, mut compare: impl FnMut(&(K, T), &(K, T)) -> Ordering) -> Vec<usize> {
+		let mut order: Vec<usize> = (0..self.items.len()).collect();
+
+		order.sort_by(|left, right| compare(&self.items[*left], &self.items[*right]));
+
+		let mut permutation = vec![0; order.len()];
+
+		for (new_index, old_index) in order.into_iter().enumerate() {
+			permutation[old_index] = new_index;
+		}
+
+		self.permute(&permutation);
+
+		permutation
+	}
+
+	/// Sorts the items by their ids so their indices no longer depend on insertion order.
+	/// Same as [`Registry::sort_by`] otherwise.
+	pub fn sort_by_id(&mut self) -> Vec<usize>
+	where
+		K: Ord,
+	{
+		self.sort_by(|(left, _), (right, _)| left.cmp(right))
+	}
+
+	/// Adds the tag to the item with the associated id or alias.
+	/// Tagging an item which already has the tag does nothing.
+	pub fn tag(&mut self, tag: impl Into<RegistryId>, registry_id: impl Borrow<K>) -> Result<(), RegistryError<K>> {
+		let registry_id = registry_id.borrow();
+		let index = *self.ids.get(registry_id).ok_or_else(|| RegistryError::MissingId(registry_id.clone()))?;
+		let indices = self.tags.entry(tag.into()).or_default();
+
+		if let Err(position) = indices.binary_search(&index) {
+			indices.insert(position, index);
+		}
+
+		Ok(())
+	}
+
+	/// Returns every tag of the item with the associated id or alias.
+	pub fn tags_of(&self, registry_id: impl Borrow<K>) -> impl Iterator<Item = &RegistryId> {
+		let index = self.ids.get(registry_id.borrow()).copied();
+
+		self.tags
+			.iter()
+			.filter(move |(_, indices)| index.is_some_and(|index| indices.binary_search(&index).is_ok()))
+			.map(|(tag, _)| tag)
+	}
+
+	/// Inserts all items only if none of their ids are already registered or duplicated in the input.
+	/// Otherwise the registry is left untouched and every conflict is reported with its index in the input.
+	pub fn try_insert_all(&mut self, registry_ids: impl IntoIterator<Item = (impl Into<K>, T)>) -> Result<(), RegistryErrors<K>> {
+		let pending: Vec<(K, T)> = registry_ids.into_iter().map(|(registry_id, item)| (registry_id.into(), item)).collect();
+
+		{
+			let mut errors = Vec::new();
+			let mut seen = HashSet::with_capacity(pending.len());
+
+			for (input_index, (registry_id, _)) in pending.iter().enumerate() {
+				if self.ids.contains_key(registry_id) || !seen.insert(registry_id) {
+					errors.push((input_index, RegistryError::DuplicateId(registry_id.clone())));
+				}
+			}
+
+			RegistryErrors::from_errors(errors)?;
+		}
+
+		self.ids.reserve(pending.len());
+		self.items.reserve(pending.len());
+
+		for (registry_id, item) in pending {
+			self.ids.insert(registry_id.clone(), self.items.len());
+			self.items.push((registry_id, item));
+		}
+
+		Ok(())
+	}
+}
+
+impl<T: RegistryItem> Registry<T> {
+	/// Inserts every item under the `source` with its name, like [`Registry::insert_all`].
+	/// Names which do not make a valid `RegistryId` are skipped and reported with their index in the input.
+	pub fn extend_namespaced(&mut self, source: &str, items: impl IntoIterator<Item = (String, T)>) -> Result<(), RegistryErrors> {
+		let mut errors = Vec::new();
+
+		for (input_index, (name, item)) in items.into_iter().enumerate() {
+			let result = match RegistryId::try_new(source, &name) {
+				Err(error) => Err(RegistryError::InvalidId(format!("{source}:{name}"), error.to_string())),
+				Ok(registry_id) => self.insert(registry_id, item),
+			};
+
+			if let Err(error) = result {
+				errors.push((input_index, error));
+			}
+		}
+
+		RegistryErrors::from_errors(errors)
+	}
+
+	/// Same as `get_mut` but looks up the id without constructing a `RegistryId`.
+	pub fn get_mut_str(&mut self, registry_id: &str) -> Option<&mut T> {
+		let index = *self.ids.get(registry_id)?;
+
+		Some(&mut self.items[index].1)
+	}
+
+	/// Same as `get` but looks up the id without constructing a `RegistryId`.
+	/// Malformed ids are not registered, so they return `None` instead of panicking.
+	pub fn get_str(&self, registry_id: &str) -> Option<&T> {
+		let index = *self.ids.get(registry_id)?;
+
+		Some(&self.items[index].1)
+	}
+
+	/// Same as `index_of` but looks up the id without constructing a `RegistryId`.
+	pub fn index_of_str(&self, registry_id: &str) -> Option<usize> {
+		self.ids.get(registry_id).copied()
+	}
+
+	/// Moves every id and alias with the `from` source to the `to` source, keeping their names and indices.
+	/// Fails without changing anything if a moved id would be invalid or collide with an id that is not moved,
+	/// reporting each conflict with the index of its item, in index order.
+	/// Tags are not changed.
+	pub fn re_source(&mut self, from: &str, to: &str) -> Result<(), RegistryErrors> {
+		if from == to {
+			return Ok(());
+		}
+
+		let mut errors = Vec::new();
+		let mut renames = Vec::new();
+
+		//in index order so the errors are the same every run
+		let mut moved: Vec<(&RegistryId, usize)> = self.ids.iter().filter(|(old, _)| old.source() == from).map(|(old, index)| (old, *index)).collect();
+		moved.sort_by(|(left_id, left_index), (right_id, right_index)| left_index.cmp(right_index).then_with(|| left_id.cmp(right_id)));
+
+		for (old, index) in moved {
+			match RegistryId::try_new(to, old.name()) {
+				Err(error) => errors.push((index, RegistryError::InvalidId(format!("{to}:{}", old.name()), error.to_string()))),
+
+				Ok(new) => {
+					if self.ids.contains_key(&new) {
+						errors.push((index, RegistryError::DuplicateId(new.clone())));
+					}
+
+					renames.push((old.clone(), new, index));
+				}
+			}
+		}
+
+		RegistryErrors::from_errors(errors)?;
+
+		for (old, _, _) in &renames {
+			self.ids.remove(old);
+		}
+
+		for (old, new, index) in renames {
+			let stored_id = &mut self.items[index].0;
+
+			if *stored_id == old {
+				*stored_id = new.clone();
+			}
+
+			self.ids.insert(new, index);
+		}
+
+		Ok(())
+	}
+}
+
+impl<T: RegistryItem, K: RegistryKey> Default for Registry<T, K> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: RegistryItem, K: RegistryKey> Index<usize> for Registry<T, K> {
+	type Output = T;
+
+	fn index(&self, index: usize) -> &Self::Output {
+		&self.items[index].1
+	}
+}
+
+impl<T: RegistryItem, K: RegistryKey> Index<&K> for Registry<T, K> {
+	type Output = T;
+
+	fn index(&self, index: &K) -> &Self::Output {
+		&self.items[*self.ids.get(index).expect("failed to index Registry")].1
+	}
+}
+
+impl<T: RegistryItem, K: RegistryKey> IndexMut<usize> for Registry<T, K> {
+	fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+		&mut self.items[index].1
+	}
+}
+
+impl<T: RegistryItem, K: RegistryKey> IndexMut<&K> for Registry<T, K> {
+	fn index_mut(&mut self, index: &K) -> &mut Self::Output {
+		&mut self.items[*self.ids.get(index).expect("failed to index Registry")].1
+	}
+}
+
+impl<T: RegistryItem, K: RegistryKey> IntoIterator for Registry<T, K> {
+	type Item = (K, T);
+	type IntoIter = std::vec::IntoIter<Self::Item>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.into_iter()
+	}
+}
+
+impl<T: RegistryItem, K: RegistryKey> RegistryCollection<T, K> for Registry<T, K> {
+	fn clear(&mut self) {
+		Registry::clear(self);
+	}
+
+	fn get_indexed_mut(&mut self, index: usize) -> Option<(&K, &mut T)> {
+		Registry::get_indexed_mut(self, index)
+	}
+
+	fn ids(&self) -> &HashMap<K, usize> {
+		&self.ids
+	}
+
+	fn insert(&mut self, registry_id: impl Into<K>, item: T) -> Result<usize, RegistryError<K>> {
+		Registry::insert(self, registry_id, item)
+	}
+
+	fn items(&self) -> &[(K, T)] {
+		&self.items
+	}
+}
+
+/// The ids affected by [`Registry::merge`].
+#[derive(Clone, Debug)]
+pub struct MergeReport<K = RegistryId> {
+	/// Ids which were not registered before the merge.
+	pub added: Vec<K>,
+
+	/// Ids which were already registered and kept their existing item.
+	pub skipped: Vec<K>,
+
+	/// Ids which were already registered and had their item replaced or resolved.
+	pub overwritten: Vec<K>,
+}
+
+impl<K> Default for MergeReport<K> {
+	fn default() -> Self {
+		Self {
+			added: Vec::new(),
+			skipped: Vec::new(),
+			overwritten: Vec::new(),
+		}
+	}
+}
+
+/// How [`Registry::merge`] handles ids that are already registered.
+pub enum MergeStrategy<T, K = RegistryId> {
+	/// Fail without merging anything, like [`Registry::try_insert_all`].
+	Error,
+
+	/// Drop the incoming item.
+	KeepExisting,
+
+	/// Replace the existing item with the incoming item.
+	Overwrite,
+
+	/// Replace the existing item with the returned item.
+	Resolve(fn(&K, &T, T) -> T),
+}
+
+impl<T, K> Clone for MergeStrategy<T, K> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<T, K> Copy for MergeStrategy<T, K> {}
+
+impl<T, K> Debug for MergeStrategy<T, K> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Error => f.write_str("Error"),
+			Self::KeepExisting => f.write_str("KeepExisting"),
+			Self::Overwrite => f.write_str("Overwrite"),
+			Self::Resolve(_) => f.write_str("Resolve"),
+		}
+	}
+}
+
+/// The difference in ids and indices between two registries, created by [`Registry::diff`].
+#[derive(Clone, Debug)]
+pub struct RegistryDiff<K = RegistryId> {
+	/// Ids registered in both, but at different indices.
+	/// Stored as the id, its index in `self`, and its index in `other`.
+	pub moved: Vec<(K, usize, usize)>,
+
+	/// Ids registered in `other` but not in `self`.
+	pub only_in_other: Vec<K>,
+
+	/// Ids registered in `self` but not in `other`.
+	pub only_in_self: Vec<K>,
+}
+
+impl<K> Default for RegistryDiff<K> {
+	fn default() -> Self {
+		Self {
+			moved: Vec::new(),
+			only_in_other: Vec::new(),
+			only_in_self: Vec::new(),
+		}
+	}
+}
+
+impl<K> RegistryDiff<K> {
+	/// Returns true if both registries have the same ids at the same indices.
+	pub fn is_empty(&self) -> bool {
+		self.moved.is_empty() && self.only_in_other.is_empty() && self.only_in_self.is_empty()
+	}
+}
+
+/// The result of [`Registry::apply_index_table`].
+#[derive(Clone, Debug)]
+pub struct RegistryRemap<K = RegistryId> {
+	/// The value at each previous index is the new index of that item.
+	pub permutation: Vec<usize>,
+
+	/// Ids that were registered but not in the table.
+	pub unlisted: Vec<K>,
+}
+
+/// Item indices grouped by a key, created by [`Registry::build_index`].
+#[derive(Clone, Debug)]
+pub struct RegistrySecondaryIndex<K: Eq + Hash> {
+	indices: HashMap<K, Vec<usize>>,
+
+	/// The amount of items in the registry when this was built.
+	len: usize,
+}
+
+impl<K: Eq + Hash> RegistrySecondaryIndex<K> {
+	/// Returns the indices of the items with the key, in registry order.
+	pub fn indices(&self, key: &K) -> &[usize] {
+		self.indices.get(key).map(Vec::as_slice).unwrap_or(&[])
+	}
+
+	pub fn keys(&self) -> impl Iterator<Item = &K> {
+		self.indices.keys()
+	}
+}
+
+/// The payload of a [`RegistryEvent`].
+#[derive(Clone, Debug, Event)]
+pub enum RegistryChange<K: RegistryKey = RegistryId> {
+	Added { id: K, index: usize },
+	Removed { id: K, index: usize },
+}
+
+/// Sent by [`RegistryWriter`] when the `Registry<T, K>` resource is changed.
+pub type RegistryEvent<T, K = RegistryId> = Sign<RegistryChange<K>, T>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum RegistryError<K: RegistryKey = RegistryId> {
+	#[error("Registry is already at its capacity of {}", .0)]
+	CapacityExceeded(usize),
+
+	#[error("Registry key {:?} is already registered", .0)]
+	DuplicateId(K),
+
+	#[error("Registry index {} does not fit in a u32", .0)]
+	IndexOverflow(usize),
+
+	#[error("{:?} is not a valid RegistryId: {}", .0, .1)]
+	InvalidId(String, String),
+
+	#[error("Registry key {:?} has an out of range or duplicate index {}", .0, .1)]
+	InvalidIndex(K, u32),
+
+	#[error("Registry key {:?} is not registered", .0)]
+	MissingId(K),
+}
+
+/// One or more [`RegistryError`]s, each paired with the index of the input which caused it.
+/// Never empty.
+#[derive(Debug)]
+pub struct RegistryErrors<K: RegistryKey = RegistryId> {
+	errors: Vec<(usize, RegistryError<K>)>,
+}
+
+impl<K: RegistryKey> RegistryErrors<K> {
+	/// Returns `Ok(())` if there are no errors.
+	pub fn from_errors(errors: Vec<(usize, RegistryError<K>)>) -> Result<(), Self> {
+		if errors.is_empty() {
+			Ok(())
+		} else {
+			Err(Self { errors })
+		}
+	}
+
+	pub fn errors(&self) -> &[(usize, RegistryError<K>)] {
+		&self.errors
+	}
+
+	pub fn into_errors(self) -> Vec<(usize, RegistryError<K>)> {
+		self.errors
+	}
+}
+
+impl<K: RegistryKey> Display for RegistryErrors<K> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{} registry error(s):", self.errors.len())?;
+
+		for (input_index, error) in &self.errors {
+			write!(f, " [{input_index}] {error};")?;
+		}
+
+		Ok(())
+	}
+}
+
+impl<K: RegistryKey> std::error::Error for RegistryErrors<K> {}
+
+/// Strings shared by every [`RegistryId`] made with [`RegistryId::intern`].
+static INTERNED_IDS: LazyLock<Mutex<HashSet<Arc<str>>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// Represents a unique identifier for a registered data type.
+/// Cloning is a reference count increment.
+/// Reflected and serialized as its string form.
+#[derive(Clone, Eq, Reflect)]
+#[cfg_attr(not(feature = "serde"), reflect_value(Debug, FromReflect, Hash, PartialEq, from_reflect = false))]
+#[cfg_attr(feature = "serde", reflect_value(Debug, Deserialize, FromReflect, Hash, PartialEq, Serialize, from_reflect = false))]
+pub struct RegistryId {
+	colon: usize,
+	string: Arc<str>,
+}
+
+impl RegistryId {
+	/// Starts building a `RegistryId` with the `source`.
+	pub fn builder(source: impl Into<String>) -> RegistryIdBuilder {
+		RegistryIdBuilder { source: source.into() }
+	}
+
+	pub fn id(&self) -> &str {
+		&self.string
+	}
+
+	/// Parses the `str` like [`FromStr`], but shares the allocation with
+	/// every other interned `RegistryId` of the same string.
+	pub fn intern(str: &str) -> anyhow::Result<Self> {
+		let colon = Self::validate(str)?;
+		let mut interned = INTERNED_IDS.lock().unwrap();
+
+		let string = match interned.get(str) {
+			Some(string) => Arc::clone(string),
+
+			None => {
+				let string: Arc<str> = Arc::from(str);
+				interned.insert(Arc::clone(&string));
+
+				string
+			}
+		};
+
+		Ok(Self { colon, string })
+	}
+
+	pub fn name(&self) -> &str {
+		&self.string[(self.colon + 1)..]
+	}
+
+	pub fn new(source: String, name: String) -> Self {
+		Self {
+			colon: source.len(),
+			string: Arc::from(format!("{source}:{name}")),
+		}
+	}
+
+	/// Returns true if both ids share the same allocation, as interned ids do.
+	pub fn ptr_eq(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.string, &other.string)
+	}
+
+	pub fn source(&self) -> &str {
+		&self.string[..self.colon]
+	}
+
+	/// Same as [`RegistryId::new`] but validates the result like [`FromStr`].
+	pub fn try_new(source: &str, name: &str) -> anyhow::Result<Self> {
+		Self::from_str(&format!("{source}:{name}"))
+	}
+
+	/// Returns the index of the colon if the `str` is a valid id.
+	fn validate(str: &str) -> anyhow::Result<usize> {
+		if !str.is_ascii() {
+			bail!("RegistryId must be ascii");
+		}
+
+		if str != str.to_lowercase() {
+			bail!("RegistryId must be all lowercase ASCII");
+		}
+
+		let mut spliterator = str.split(":");
+
+		//empty sources and names are rejected here
+		fn starts_with_lowercase_letter(str: &str) -> bool {
+			str.bytes().next().is_some_and(|byte| byte.is_ascii_lowercase())
+		}
+
+		match (spliterator.next(), spliterator.next(), spliterator.next()) {
+			(Some(source), Some(name), None) => {
+				if !(starts_with_lowercase_letter(source) && starts_with_lowercase_letter(name)) {
+					bail!("RegistryId input string must start with a lower-case ASCII letter");
+				}
+
+				if !(source.is_alpha_numeric() && name.is_alpha_numeric()) {
+					bail!("RegistryId input string must start with a lower-case ASCII letter");
+				}
+
+				Ok(source.len())
+			}
+
+			_ => Err(anyhow!("RegistryId expected format \"source:name\" (which was not provided)")),
+		}
+	}
+}
+
+impl AsRef<RegistryId> for RegistryId {
+	fn as_ref(&self) -> &RegistryId {
+		self
+	}
+}
+
+/// Hashes and compares the same as the full id string, allowing lookups by `&str`.
+impl Borrow<str> for RegistryId {
+	fn borrow(&self) -> &str {
+		&self.string
+	}
+}
+
+impl Debug for RegistryId {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_tuple("RegistryId").field(&&*self.string).finish()
+	}
+}
+
+impl Display for RegistryId {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(&self.string)
+	}
+}
+
+impl FromReflect for RegistryId {
+	/// Accepts a reflected `RegistryId`, or a reflected `String` which is validated like [`FromStr`].
+	fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+		if let Some(registry_id) = reflect.downcast_ref::<RegistryId>() {
+			return Some(registry_id.clone());
+		}
+
+		Self::from_str(reflect.downcast_ref::<String>()?).ok()
+	}
+}
+
+impl FromStr for RegistryId {
+	type Err = anyhow::Error;
+
+	fn from_str(str: &str) -> Result<Self, Self::Err> {
+		Ok(Self {
+			colon: Self::validate(str)?,
+			string: Arc::from(str),
+		})
+	}
+}
+
+impl From<&str> for RegistryId {
+	fn from(value: &str) -> Self {
+		Self::from_str(value).unwrap()
+	}
+}
+
+impl From<(&str, &str)> for RegistryId {
+	fn from(value: (&str, &str)) -> Self {
+		Self::new(value.0.to_owned(), value.1.to_owned())
+	}
+}
+
+impl From<[&str; 2]> for RegistryId {
+	fn from(value: [&str; 2]) -> Self {
+		Self::new(value[0].to_owned(), value[1].to_owned())
+	}
+}
+
+impl From<String> for RegistryId {
+	fn from(value: String) -> Self {
+		Self::from_str(&value).unwrap()
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for RegistryId {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		Self::from_str(&String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+	}
+}
+
+impl Hash for RegistryId {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.string.hash(state);
+	}
+}
+
+impl Ord for RegistryId {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.string.cmp(&other.string)
+	}
+}
+
+impl PartialEq for RegistryId {
+	fn eq(&self, other: &Self) -> bool {
+		self.string == other.string
+	}
+}
+
+impl PartialOrd for RegistryId {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		self.string.partial_cmp(&other.string)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for RegistryId {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(&self.string)
+	}
+}
+
+/// Creates [`RegistryId`]s with the same source, made by [`RegistryId::builder`].
+#[derive(Clone, Debug)]
+pub struct RegistryIdBuilder {
+	source: String,
+}
+
+impl RegistryIdBuilder {
+	/// Uses the `name` as-is, failing if it or the source is not valid.
+	pub fn name(&self, name: &str) -> anyhow::Result<RegistryId> {
+		RegistryId::try_new(&self.source, name)
+	}
+
+	/// Sanitizes the `display` name with [`sanitize_registry_name`] first.
+	/// Only fails if nothing usable remains in the `display` name, or the source is not valid.
+	pub fn name_from_display(&self, display: &str) -> anyhow::Result<RegistryId> {
+		let name = sanitize_registry_name(display).ok_or_else(|| anyhow!("RegistryId name could not be made from {display:?}"))?;
+
+		RegistryId::try_new(&self.source, &name)
+	}
+
+	pub fn source(&self) -> &str {
+		&self.source
+	}
+}
+
+/// Implemented by [`Registry`] and `FixedRegistry`, so both have the same lookups.
+/// Only the storage specific methods need to be implemented.
+pub trait RegistryCollection<T: RegistryItem, K: RegistryKey> {
+	/// Clears all items and ids from the registry.
+	fn clear(&mut self);
+
+	/// Returns the id and a mutable reference to the item at the provided index.
+	fn get_indexed_mut(&mut self, index: usize) -> Option<(&K, &mut T)>;
+
+	/// The ids mapped to the index of their item.
+	fn ids(&self) -> &HashMap<K, usize>;
+
+	/// Inserts a new RegistryItem into the registry.
+	fn insert(&mut self, registry_id: impl Into<K>, item: T) -> Result<usize, RegistryError<K>>;
+
+	/// The registered items, in index order.
+	fn items(&self) -> &[(K, T)];
+
+	/// Returns a reference to the registry item with the associated id.
+	fn get<'a>(&'a self, registry_id: impl Borrow<K>) -> Option<&'a T>
+	where
+		K: 'a,
+	{
+		self.get_indexed(self.index_of(registry_id)?).map(|(_, item)| item)
+	}
+
+	/// Returns the id and item at the provided index.
+	fn get_indexed(&self, index: usize) -> Option<(&K, &T)> {
+		self.items().get(index).map(|(registry_id, item)| (registry_id, item))
+	}
+
+	/// Returns a mutable reference to the registry item with the associated id.
+	fn get_mut<'a>(&'a mut self, registry_id: impl Borrow<K>) -> Option<&'a mut T>
+	where
+		K: 'a,
+	{
+		let index = self.index_of(registry_id)?;
+
+		self.get_indexed_mut(index).map(|(_, item)| item)
+	}
+
+	/// Returns the key of the item at the provided index.
+	fn id_of<'a>(&'a self, index: usize) -> Option<&'a K>
+	where
+		T: 'a,
+	{
+		self.items().get(index).map(|(registry_id, _)| registry_id)
+	}
+
+	/// Returns the index in the registry at which the associated item is located.
+	fn index_of(&self, registry_id: impl Borrow<K>) -> Option<usize> {
+		self.ids().get(registry_id.borrow()).copied()
+	}
+
+	fn is_empty(&self) -> bool {
+		self.items().is_empty()
+	}
+
+	fn len(&self) -> usize {
+		self.items().len()
+	}
+}
+
+/// Implemented by data types that can be added to a `Registry<T>`.
+pub trait RegistryItem: Debug {}
+
+impl<T: Deref + Debug> RegistryItem for T where <T as Deref>::Target: RegistryItem {}
+
+/// Implemented by every type that can be used as the key of a `Registry<T, K>`.
+/// Only [`RegistryId`] is validated, other keys are used as-is.
+pub trait RegistryKey: Clone + Debug + Eq + Hash {}
+
+impl<K: Clone + Debug + Eq + Hash> RegistryKey for K {}
+
+/// Adds [`RegisterRegistryTypes::register_registry_types`] to [`App`].
+pub trait RegisterRegistryTypes {
+	/// Registers `RegistryId` and `Registry<T>` in the type registry.
+	fn register_registry_types<T: RegistryItem + Clone + TypePath + Send + Sync>(&mut self) -> &mut Self;
+}
+
+impl RegisterRegistryTypes for App {
+	fn register_registry_types<T: RegistryItem + Clone + TypePath + Send + Sync>(&mut self) -> &mut Self {
+		self.register_type::<RegistryId>().register_type::<Registry<T>>()
+	}
+}
+
+/// Read access to the `Registry<T>` resource with lookup helpers.
+#[derive(SystemParam)]
+pub struct Reg<'w, 's, T: RegistryItem + Send + Sync + 'static> {
+	registry: Res<'w, Registry<T>>,
+
+	/// Canonical ids and indices found by `handle`, keyed by the requested id or alias.
+	/// Kept between runs of the system.
+	handles: Local<'s, HashMap<String, (RegistryId, usize)>>,
+}
+
+impl<'w, 's, T: RegistryItem + Send + Sync + 'static> Reg<'w, 's, T> {
+	/// Same as [`Registry::get`] but panics with an error log naming the id if it is not registered.
+	pub fn expect(&self, registry_id: &str) -> &T {
+		match self.registry.get_str(registry_id) {
+			Some(item) => item,
+
+			None => {
+				error!("Registry<{}> has no item registered as {registry_id:?}", std::any::type_name::<T>());
+
+				panic!("Registry<{}> has no item registered as {registry_id:?}", std::any::type_name::<T>());
+			}
+		}
+	}
+
+	/// Same as [`Registry::get_str`].
+	pub fn get(&self, registry_id: &str) -> Option<&T> {
+		self.registry.get_str(registry_id)
+	}
+
+	/// Returns the index of the id or alias, which is cached for later calls from the same system.
+	/// The cached index is checked against the registry so changed indices are found again.
+	pub fn handle(&mut self, registry_id: &str) -> Option<usize> {
+		if let Some((canonical_id, index)) = self.handles.get(registry_id) {
+			if self.registry.items.get(*index).is_some_and(|(item_id, _)| item_id == canonical_id) {
+				return Some(*index);
+			}
+		}
+
+		let index = self.registry.index_of_str(registry_id)?;
+
+		self.handles.insert(registry_id.to_owned(), (self.registry.items[index].0.clone(), index));
+
+		Some(index)
+	}
+}
+
+impl<'w, 's, T: RegistryItem + Send + Sync + 'static> Deref for Reg<'w, 's, T> {
+	type Target = Registry<T>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.registry
+	}
+}
+
+/// Adds the `Registry<T, K>` resource and its [`RegistryEvent`].
+pub struct RegistryPlugin<T: RegistryItem, K: RegistryKey = RegistryId> {
+	/// Ran in the [`Startup`] schedule to fill the registry.
+	populate: Option<fn(&mut Registry<T, K>)>,
+
+	phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: RegistryItem, K: RegistryKey> RegistryPlugin<T, K> {
+	pub fn new() -> Self {
+		Self {
+			populate: None,
+			phantom: PhantomData,
+		}
+	}
+
+	/// Sets a function which fills the registry in the [`Startup`] schedule.
+	pub fn with_populate(mut self, populate: fn(&mut Registry<T, K>)) -> Self {
+		self.populate = Some(populate);
+
+		self
+	}
+}
+
+impl<T: RegistryItem, K: RegistryKey> Default for RegistryPlugin<T, K> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: RegistryItem + Send + Sync + 'static, K: RegistryKey + Send + Sync + 'static> Plugin for RegistryPlugin<T, K> {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<Registry<T, K>>().add_event::<RegistryEvent<T, K>>();
+
+		if let Some(populate) = self.populate {
+			app.add_systems(Startup, move |mut registry: ResMut<Registry<T, K>>| populate(&mut registry));
+		}
+	}
+}
+
+/// Mutates the `Registry<T, K>` resource and sends a [`RegistryEvent`] for each change.
+#[derive(SystemParam)]
+pub struct RegistryWriter<'w, T: RegistryItem + Send + Sync + 'static, K: RegistryKey + Send + Sync + 'static = RegistryId> {
+	registry: ResMut<'w, Registry<T, K>>,
+	events: EventWriter<'w, RegistryEvent<T, K>>,
+}
+
+impl<'w, T: RegistryItem + Send + Sync + 'static, K: RegistryKey + Send + Sync + 'static> RegistryWriter<'w, T, K> {
+	/// [`Registry::insert`] which sends [`RegistryChange::Added`] on success.
+	pub fn insert(&mut self, registry_id: impl Into<K>, item: T) -> Result<usize, RegistryError<K>> {
+		let registry_id = registry_id.into();
+		let index = self.registry.insert(registry_id.clone(), item)?;
+
+		self.events.send(RegistryEvent::new(RegistryChange::Added { id: registry_id, index }));
+
+		Ok(index)
+	}
+
+	/// [`Registry::remove`] which sends [`RegistryChange::Removed`] on success.
+	/// The event has the canonical id of the item, even when removed by an alias.
+	pub fn remove(&mut self, registry_id: impl Borrow<K>) -> Option<(usize, T)> {
+		let registry_id = registry_id.borrow();
+		let canonical_id = self.registry.entry_of(registry_id)?.1.clone();
+		let (index, item) = self.registry.remove(registry_id)?;
+
+		self.events.send(RegistryEvent::new(RegistryChange::Removed { id: canonical_id, index }));
+
+		Some((index, item))
+	}
+}
+
+impl<'w, T: RegistryItem + Send + Sync + 'static, K: RegistryKey + Send + Sync + 'static> Deref for RegistryWriter<'w, T, K> {
+	type Target = Registry<T, K>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.registry
+	}
+}
+
+/// A snapshot of an [`ArcRegistry`] which does not keep its items alive.
+/// Created by [`ArcRegistry::weak_registry`].
+pub struct WeakRegistry<T, K = RegistryId> {
+	weaks: HashMap<K, Weak<T>>,
+}
+
+impl<T, K: RegistryKey> WeakRegistry<T, K> {
+	/// Upgrades the `Weak` of the associated id.
+	/// Returns `None` if the id was not in the snapshot or the item was dropped.
+	pub fn get(&self, registry_id: impl Borrow<K>) -> Option<Arc<T>> {
+		self.weaks.get(registry_id.borrow())?.upgrade()
+	}
+
+	pub fn get_weak(&self, registry_id: impl Borrow<K>) -> Option<&Weak<T>> {
+		self.weaks.get(registry_id.borrow())
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.weaks.is_empty()
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = (&K, &Weak<T>)> {
+		self.weaks.iter()
+	}
+
+	pub fn len(&self) -> usize {
+		self.weaks.len()
+	}
+}
+
+impl<T> WeakRegistry<T> {
+	pub fn get_str(&self, registry_id: &str) -> Option<Arc<T>> {
+		self.weaks.get(registry_id)?.upgrade()
+	}
+}
+
+impl<T, K: Clone> Clone for WeakRegistry<T, K> {
+	fn clone(&self) -> Self {
+		Self { weaks: self.weaks.clone() }
+	}
+}
+
+impl<T: Debug, K: Debug> Debug for WeakRegistry<T, K> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("WeakRegistry").field("weaks", &self.weaks).finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[derive(Clone, Debug, PartialEq)]
+	struct Item(u32);
+
+	impl RegistryItem for Item {}
+
+	fn registry(ids: &[&str]) -> Registry<Item> {
+		let mut registry = Registry::new();
+
+		for (index, id) in ids.iter().enumerate() {
+			registry.insert(*id, Item(index as u32)).unwrap();
+		}
+
+		registry
+	}
+
+	#[test]
+	fn apply_index_table_matches_server_order() {
+		let server = registry(&["test:a", "test:b", "test:c", "test:d"]);
+		let mut client = registry(&["test:c", "test:a", "test:d", "test:b"]);
+
+		assert!(!server.diff(&client).is_empty());
+
+		let remap = client.apply_index_table(server.export_index_table().unwrap()).unwrap();
+
+		assert!(server.diff(&client).is_empty());
+		assert!(remap.unlisted.is_empty());
+		assert_eq!(client.get_str("test:c"), Some(&Item(0)));
+		assert_eq!(client.index_of_str("test:c"), Some(2));
+	}
+
+	#[test]
+	fn apply_index_table_rejects_duplicate_id() {
+		let mut registry = registry(&["test:a", "test:b"]);
+		let id = RegistryId::from("test:a");
+		let errors = registry.apply_index_table([(id.clone(), 0), (id.clone(), 1)]).unwrap_err();
+
+		assert!(matches!(errors.errors(), [(1, RegistryError::DuplicateId(duplicate))] if *duplicate == id));
+		assert_eq!(registry.index_of_str("test:a"), Some(0));
+		assert_eq!(registry.index_of_str("test:b"), Some(1));
+	}
+
+	#[test]
+	fn empty_parts_are_invalid() {
+		for str in ["", ":", ":name", "source:", "source:name:"] {
+			assert!(RegistryId::from_str(str).is_err(), "{str:?} should be invalid");
+		}
+
+		assert!(RegistryId::try_new("", "name").is_err());
+		assert!(RegistryId::try_new("source", "").is_err());
+		assert!(RegistryId::try_new("source", "name").is_ok());
+	}
+
+	#[test]
+	fn from_reflect_validates_strings() {
+		let valid: Box<dyn Reflect> = Box::new(String::from("source:name"));
+		let empty: Box<dyn Reflect> = Box::new(String::new());
+		let missing_name: Box<dyn Reflect> = Box::new(String::from("source:"));
+
+		assert_eq!(RegistryId::from_reflect(valid.as_ref()), Some(RegistryId::from("source:name")));
+		assert_eq!(RegistryId::from_reflect(empty.as_ref()), None);
+		assert_eq!(RegistryId::from_reflect(missing_name.as_ref()), None);
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn reflect_serde_round_trip() {
+		use bevy::reflect::serde::{ReflectDeserializer, ReflectSerializer};
+		use bevy::reflect::TypeRegistry;
+		use serde::de::DeserializeSeed;
+
+		let mut type_registry = TypeRegistry::new();
+		type_registry.register::<RegistryId>();
+
+		let registry_id = RegistryId::from("source:name");
+		let serialized = toml::to_string(&ReflectSerializer::new(&registry_id, &type_registry)).unwrap();
+		let reflect = ReflectDeserializer::new(&type_registry).deserialize(toml::Deserializer::new(&serialized)).unwrap();
+
+		assert_eq!(RegistryId::from_reflect(reflect.as_ref()), Some(registry_id));
+
+		//an invalid string must be an error, not a panic
+		let invalid = serialized.replace("source:name", "source:");
+
+		assert!(ReflectDeserializer::new(&type_registry).deserialize(toml::Deserializer::new(&invalid)).is_err());
+		assert!(toml::from_str::<HashMap<String, RegistryId>>("id = \"\"").is_err());
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn dynamic_scene_round_trip() {
+		use bevy::ecs::entity::EntityHashMap;
+		use bevy::prelude::{AppTypeRegistry, Component, ReflectComponent, World};
+		use bevy::scene::ron::de::Deserializer;
+		use bevy::scene::serde::SceneDeserializer;
+		use bevy::scene::DynamicScene;
+		use serde::de::DeserializeSeed;
+
+		#[derive(Component, Debug, PartialEq, Reflect)]
+		#[reflect(Component)]
+		struct Held {
+			item: RegistryId,
+			fallback: Option<RegistryId>,
+		}
+
+		let type_registry = AppTypeRegistry::default();
+
+		{
+			let mut type_registry = type_registry.write();
+			type_registry.register::<Held>();
+			type_registry.register::<Option<RegistryId>>();
+			type_registry.register::<RegistryId>();
+		}
+
+		let held = || Held {
+			item: RegistryId::from("source:sword"),
+			fallback: Some(RegistryId::from("source:stick")),
+		};
+
+		let mut world = World::new();
+		world.insert_resource(type_registry.clone());
+		world.spawn(held());
+
+		let serialized = DynamicScene::from_world(&world).serialize(&type_registry.read()).unwrap();
+
+		assert!(serialized.contains("\"source:sword\""), "{serialized}");
+
+		let mut deserializer = Deserializer::from_str(&serialized).unwrap();
+		let scene = SceneDeserializer {
+			type_registry: &type_registry.read(),
+		}
+		.deserialize(&mut deserializer)
+		.unwrap();
+
+		let mut loaded_world = World::new();
+		loaded_world.insert_resource(type_registry.clone());
+		scene.write_to_world(&mut loaded_world, &mut EntityHashMap::default()).unwrap();
+
+		let loaded: Vec<&Held> = loaded_world.query::<&Held>().iter(&loaded_world).collect();
+
+		assert_eq!(loaded, [&held()]);
+
+		//an invalid id fails the whole scene
+		let invalid = serialized.replace("source:sword", "source:");
+		let mut deserializer = Deserializer::from_str(&invalid).unwrap();
+
+		assert!(SceneDeserializer {
+			type_registry: &type_registry.read(),
+		}
+		.deserialize(&mut deserializer)
+		.is_err());
+	}
+
+	#[test]
+	fn builder_rejects_empty_parts() {
+		assert!(RegistryId::builder("").name("name").is_err());
+		assert!(RegistryId::builder("").name_from_display("Name").is_err());
+		assert!(RegistryId::builder("source").name("").is_err());
+		assert!(RegistryId::builder("source").name_from_display("").is_err());
+		assert!(RegistryId::builder("source").name_from_display("!?").is_err());
+	}
+
+	#[test]
+	fn builder_name_from_display() {
+		let builder = RegistryId::builder("source");
+
+		assert_eq!(builder.name_from_display("Éclair Tower").unwrap(), RegistryId::from("source:clair_tower"));
+		assert_eq!(builder.name_from_display("1234").unwrap(), RegistryId::from("source:n1234"));
+		assert_eq!(builder.name_from_display("already_valid").unwrap(), RegistryId::from("source:already_valid"));
+		assert_eq!(builder.name("already_valid").unwrap(), RegistryId::from("source:already_valid"));
+	}
+
+	#[test]
+	fn writer_remove_by_alias_sends_canonical_id() {
+		use bevy::app::Update;
+		use bevy::prelude::{EventReader, IntoSystemConfigs};
+
+		#[derive(Default, Resource)]
+		struct Removed(Vec<(RegistryId, usize)>);
+
+		let mut app = App::new();
+
+		app.add_plugins(RegistryPlugin::<Item>::new()).init_resource::<Removed>().add_systems(
+			Update,
+			(
+				|mut writer: RegistryWriter<Item>| {
+					writer.remove(RegistryId::from("test:alias"));
+				},
+				|mut events: EventReader<RegistryEvent<Item>>, mut removed: ResMut<Removed>| {
+					for event in events.read() {
+						if let RegistryChange::Removed { id, index } = event.as_inner() {
+							removed.0.push((id.clone(), *index));
+						}
+					}
+				},
+			)
+				.chain(),
+		);
+
+		let mut registry = app.world_mut().resource_mut::<Registry<Item>>();
+
+		registry.insert("test:a", Item(0)).unwrap();
+		registry.insert("test:b", Item(1)).unwrap();
+		registry.alias("test:alias", RegistryId::from("test:b")).unwrap();
+
+		app.update();
+
+		assert_eq!(app.world().resource::<Removed>().0, [(RegistryId::from("test:b"), 1)]);
+		assert_eq!(app.world().resource::<Registry<Item>>().get_str("test:alias"), None);
+	}
+
+	#[test]
+	fn writer_insert_is_read_in_same_frame() {
+		use bevy::app::Update;
+		use bevy::prelude::{EventReader, IntoSystemConfigs};
+
+		#[derive(Default, Resource)]
+		struct Added(Vec<(RegistryId, usize)>);
+
+		let mut app = App::new();
+
+		app.add_plugins(RegistryPlugin::<Item>::new()).init_resource::<Added>().add_systems(
+			Update,
+			(
+				|mut writer: RegistryWriter<Item>, mut inserted: Local<bool>| {
+					if !*inserted {
+						*inserted = true;
+
+						writer.insert("test:a", Item(0)).unwrap();
+						writer.insert("test:b", Item(1)).unwrap();
+
+						//failed inserts send nothing
+						assert!(writer.insert("test:a", Item(2)).is_err());
+					}
+				},
+				|mut events: EventReader<RegistryEvent<Item>>, mut added: ResMut<Added>| {
+					for event in events.read() {
+						if let RegistryChange::Added { id, index } = event.as_inner() {
+							added.0.push((id.clone(), *index));
+						}
+					}
+				},
+			)
+				.chain(),
+		);
+
+		app.update();
+
+		let expected = [(RegistryId::from("test:a"), 0), (RegistryId::from("test:b"), 1)];
+
+		assert_eq!(app.world().resource::<Added>().0, expected);
+
+		//the events are not read twice
+		app.update();
+
+		assert_eq!(app.world().resource::<Added>().0, expected);
+	}
+
+	#[test]
+	fn reg_handle_with_populate() {
+		use bevy::app::Update;
+
+		#[derive(Default, Resource)]
+		struct Found(Vec<(Option<usize>, Option<usize>, bool)>);
+
+		fn populate(registry: &mut Registry<Item>) {
+			registry.insert("test:a", Item(0)).unwrap();
+			registry.insert("test:b", Item(1)).unwrap();
+			registry.alias("test:alias", RegistryId::from("test:b")).unwrap();
+		}
+
+		let mut app = App::new();
+
+		app.add_plugins(RegistryPlugin::<Item>::new().with_populate(populate)).init_resource::<Found>().add_systems(
+			Update,
+			|mut reg: Reg<Item>, mut found: ResMut<Found>| {
+				assert_eq!(reg.expect("test:alias"), &Item(1));
+				assert_eq!(reg.get("test:b"), Some(&Item(1)));
+				assert_eq!(reg.get("test:missing"), None);
+
+				let canonical = reg.handle("test:b");
+				let alias = reg.handle("test:alias");
+
+				//both the canonical id and the alias are cached under the requested string
+				found.0.push((canonical, alias, reg.handles.contains_key("test:alias")));
+				assert_eq!(reg.handle("test:missing"), None);
+			},
+		);
+
+		app.update();
+		app.update();
+
+		//the second run is served from the cache
+		assert_eq!(app.world().resource::<Found>().0, [(Some(1), Some(1), true); 2]);
+
+		//a changed index is found again
+		app.world_mut().resource_mut::<Registry<Item>>().remove(RegistryId::from("test:a"));
+		app.world_mut().resource_mut::<Found>().0.clear();
+		app.update();
+
+		assert_eq!(app.world().resource::<Found>().0, [(Some(0), Some(0), true)]);
+	}
+
+	fn incoming() -> Registry<Item> {
+		let mut registry = Registry::new();
+
+		registry.insert("test:b", Item(10)).unwrap();
+		registry.insert("test:c", Item(11)).unwrap();
+
+		registry
+	}
+
+	#[test]
+	fn merge_disjoint() {
+		for strategy in [MergeStrategy::Error, MergeStrategy::KeepExisting, MergeStrategy::Overwrite, MergeStrategy::Resolve(|_, _, item| item)] {
+			let mut registry = registry(&["test:a"]);
+			let report = registry.merge(incoming(), strategy).unwrap();
+
+			assert_eq!(report.added, [RegistryId::from("test:b"), RegistryId::from("test:c")], "{strategy:?}");
+			assert!(report.skipped.is_empty() && report.overwritten.is_empty(), "{strategy:?}");
+			assert_eq!(registry.index_of_str("test:a"), Some(0));
+			assert_eq!(registry.get_str("test:c"), Some(&Item(11)));
+		}
+	}
+
+	#[test]
+	fn merge_overlapping_error() {
+		let mut registry = registry(&["test:a", "test:b"]);
+		let errors = registry.merge(incoming(), MergeStrategy::Error).unwrap_err();
+
+		assert!(matches!(errors.errors(), [(0, RegistryError::DuplicateId(_))]));
+		assert_eq!(registry.items().len(), 2);
+		assert_eq!(registry.get_str("test:b"), Some(&Item(1)));
+	}
+
+	#[test]
+	fn merge_overlapping_keep_existing() {
+		let mut registry = registry(&["test:a", "test:b"]);
+		let report = registry.merge(incoming(), MergeStrategy::KeepExisting).unwrap();
+
+		assert_eq!(report.added, [RegistryId::from("test:c")]);
+		assert_eq!(report.skipped, [RegistryId::from("test:b")]);
+		assert!(report.overwritten.is_empty());
+		assert_eq!(registry.get_str("test:b"), Some(&Item(1)));
+		assert_eq!(registry.index_of_str("test:c"), Some(2));
+	}
+
+	#[test]
+	fn merge_overlapping_overwrite() {
+		let mut registry = registry(&["test:a", "test:b"]);
+		let report = registry.merge(incoming(), MergeStrategy::Overwrite).unwrap();
+
+		assert_eq!(report.added, [RegistryId::from("test:c")]);
+		assert_eq!(report.overwritten, [RegistryId::from("test:b")]);
+		assert!(report.skipped.is_empty());
+		assert_eq!(registry.get_str("test:b"), Some(&Item(10)));
+		assert_eq!(registry.index_of_str("test:b"), Some(1));
+	}
+
+	#[test]
+	fn merge_overlapping_resolve() {
+		let mut registry = registry(&["test:a", "test:b"]);
+		let report = registry.merge(incoming(), MergeStrategy::Resolve(|_, existing, item| Item(existing.0 + item.0))).unwrap();
+
+		assert_eq!(report.added, [RegistryId::from("test:c")]);
+		assert_eq!(report.overwritten, [RegistryId::from("test:b")]);
+		assert_eq!(registry.get_str("test:b"), Some(&Item(11)));
+	}
+
+	#[test]
+	fn sort_by_id_keeps_lookups() {
+		let mut registry = registry(&["test:c", "test:a", "test:d", "test:b"]);
+
+		registry.alias("test:alias", RegistryId::from("test:d")).unwrap();
+		registry.tag("test:tag", RegistryId::from("test:a")).unwrap();
+
+		let before: Vec<RegistryId> = registry.items().iter().map(|(registry_id, _)| registry_id.clone()).collect();
+		let permutation = registry.sort_by_id();
+
+		assert_eq!(permutation, [2, 0, 3, 1]);
+
+		//every previous index maps to the same id at its new index
+		for (old_index, registry_id) in before.iter().enumerate() {
+			assert_eq!(registry.id_of(permutation[old_index]), Some(registry_id));
+		}
+
+		assert_eq!(registry.get_str("test:c"), Some(&Item(0)));
+		assert_eq!(registry.get_str("test:alias"), Some(&Item(2)));
+		assert_eq!(registry.index_of_str("test:alias"), Some(3));
+		assert!(registry.has_tag(RegistryId::from("test:tag"), RegistryId::from("test:a")));
+		assert!(!registry.has_tag(RegistryId::from("test:tag"), RegistryId::from("test:c")));
+	}
+
+	#[test]
+	fn sort_by_permutation_round_trips() {
+		let mut registry = registry(&["test:a", "test:b", "test:c", "test:d"]);
+		let original = registry.clone();
+		let permutation = registry.sort_by(|(_, left), (_, right)| right.0.cmp(&left.0));
+
+		assert_eq!(permutation, [3, 2, 1, 0]);
+
+		//sorting by the previous indices undoes the permutation
+		let restored = registry.sort_by(|(left, _), (right, _)| original.index_of(left).cmp(&original.index_of(right)));
+
+		for (old_index, new_index) in permutation.iter().enumerate() {
+			assert_eq!(restored[*new_index], old_index);
+		}
+
+		assert!(registry.diff(&original).is_empty());
+	}
+
+	#[test]
+	fn interned_ids_share_allocation() {
+		let first = RegistryId::intern("test:interned").unwrap();
+		let second = RegistryId::intern("test:interned").unwrap();
+		let parsed = RegistryId::from("test:interned");
+
+		assert!(first.ptr_eq(&second));
+		assert!(!first.ptr_eq(&parsed));
+		assert_eq!(first, parsed);
+		assert!(!first.ptr_eq(&RegistryId::intern("test:other").unwrap()));
+		assert!(RegistryId::intern("test:").is_err());
+	}
+
+	#[test]
+	fn arc_replace_keeps_old_holders() {
+		let mut registry = ArcRegistry::<Item>::new();
+		registry.insert("test:a", Item(0)).unwrap();
+
+		let held = registry.get_arc(RegistryId::from("test:a")).unwrap();
+		let previous = registry.replace(RegistryId::from("test:a"), Item(1)).unwrap();
+
+		assert!(Arc::ptr_eq(&held, &previous));
+		assert_eq!(*held, Item(0));
+		assert_eq!(registry.get_ref_str("test:a"), Some(&Item(1)));
+		assert_eq!(registry.index_of_str("test:a"), Some(0));
+		assert!(registry.replace(RegistryId::from("test:missing"), Item(2)).is_none());
+		assert!(registry.get_str("test:missing").is_none());
+	}
+
+	#[test]
+	fn arc_strong_count() {
+		let mut registry = ArcRegistry::<Item>::new();
+		registry.insert("test:a", Item(0)).unwrap();
+
+		let id = RegistryId::from("test:a");
+
+		assert_eq!(registry.strong_count(&id), Some(0));
+
+		let first = registry.get_arc(&id).unwrap();
+		let second = registry.get_arc_str("test:a").unwrap();
+
+		assert_eq!(registry.strong_count(&id), Some(2));
+
+		//weak references are not counted
+		let _weak = registry.get_weak(&id).unwrap();
+
+		drop((first, second));
+
+		assert_eq!(registry.strong_count(&id), Some(0));
+		assert_eq!(registry.strong_count(RegistryId::from("test:missing")), None);
+	}
+
+	#[test]
+	fn weak_registry_upgrades_until_dropped() {
+		let mut registry = ArcRegistry::<Item>::new();
+		registry.insert("test:a", Item(0)).unwrap();
+		registry.insert("test:b", Item(1)).unwrap();
+
+		let weak = registry.weak_registry();
+
+		assert_eq!(weak.len(), 2);
+		assert_eq!(weak.get_str("test:a").as_deref(), Some(&Item(0)));
+		assert!(weak.get_str("test:missing").is_none());
+
+		//the snapshot does not keep the items alive or see later inserts
+		let held = registry.get_arc_str("test:b").unwrap();
+
+		registry.remove(RegistryId::from("test:a"));
+		registry.replace(RegistryId::from("test:b"), Item(2));
+		registry.insert("test:c", Item(3)).unwrap();
+
+		assert!(weak.get_str("test:a").is_none());
+		assert!(weak.get_weak(RegistryId::from("test:a")).is_some());
+		assert_eq!(weak.get_str("test:b").as_deref(), Some(&Item(1)));
+		assert!(weak.get_str("test:c").is_none());
+
+		drop(held);
+
+		assert!(weak.get_str("test:b").is_none());
+		assert_eq!(registry.weak_registry().get_str("test:b").as_deref(), Some(&Item(2)));
+	}
+
+	#[test]
+	fn indexed_lookups_on_empty_registry() {
+		let mut registry = Registry::<Item>::new();
+
+		assert_eq!(registry.get_indexed(0), None);
+		assert_eq!(registry.get_indexed_mut(0), None);
+		assert_eq!(registry.entry_of(RegistryId::from("test:a")), None);
+		assert_eq!(registry.id_of(0), None);
+		assert_eq!(registry.id_of(usize::MAX), None);
+	}
+
+	#[test]
+	fn indexed_lookups_on_populated_registry() {
+		let mut registry = registry(&["test:a", "test:b", "test:c"]);
+		registry.alias("test:alias", RegistryId::from("test:c")).unwrap();
+
+		let (a, c) = (RegistryId::from("test:a"), RegistryId::from("test:c"));
+
+		assert_eq!(registry.get_indexed(0), Some((&a, &Item(0))));
+		assert_eq!(registry.get_indexed(2), Some((&c, &Item(2))));
+		assert_eq!(registry.get_indexed(3), None);
+		assert_eq!(registry.id_of(1), Some(&RegistryId::from("test:b")));
+		assert_eq!(registry.id_of(3), None);
+
+		//aliases resolve to the canonical id
+		assert_eq!(registry.entry_of(RegistryId::from("test:alias")), Some((2, &c, &Item(2))));
+		assert_eq!(registry.entry_of(&a), Some((0, &a, &Item(0))));
+		assert_eq!(registry.entry_of(RegistryId::from("test:missing")), None);
+
+		let (registry_id, item) = registry.get_indexed_mut(1).unwrap();
+
+		assert_eq!(registry_id, &RegistryId::from("test:b"));
+		item.0 = 10;
+
+		assert_eq!(registry.get_str("test:b"), Some(&Item(10)));
+		assert_eq!(registry.get_indexed_mut(3), None);
+		assert_eq!(registry.get_indexed_mut(usize::MAX), None);
+	}
+
+	#[test]
+	fn items_with_multiple_tags() {
+		let mut registry = registry(&["test:a", "test:b", "test:c"]);
+		let (a, b) = (RegistryId::from("test:a"), RegistryId::from("test:b"));
+		let (heavy, metal, rare) = (RegistryId::from("tag:heavy"), RegistryId::from("tag:metal"), RegistryId::from("tag:rare"));
+
+		registry.tag(heavy.clone(), &a).unwrap();
+		registry.tag(metal.clone(), &a).unwrap();
+		registry.tag(rare.clone(), &a).unwrap();
+		registry.tag(metal.clone(), &b).unwrap();
+
+		//tagging twice does nothing
+		registry.tag(metal.clone(), &a).unwrap();
+
+		let mut tags: Vec<&RegistryId> = registry.tags_of(&a).collect();
+		tags.sort();
+
+		assert_eq!(tags, [&heavy, &metal, &rare]);
+		assert_eq!(registry.tags_of(&b).collect::<Vec<_>>(), [&metal]);
+		assert_eq!(registry.tags_of(RegistryId::from("test:c")).count(), 0);
+		assert_eq!(registry.iter_tag(&metal).count(), 2);
+		assert!(registry.has_tag(&rare, &a) && !registry.has_tag(&rare, &b));
+		assert!(matches!(registry.tag(rare, RegistryId::from("test:missing")), Err(RegistryError::MissingId(_))));
+
+		//removing an item removes it from every tag
+		registry.remove(&a);
+
+		assert_eq!(registry.iter_tag(&metal).map(|(registry_id, _)| registry_id).collect::<Vec<_>>(), [&b]);
+		assert_eq!(registry.iter_tag(&heavy).count(), 0);
+	}
+
+	#[test]
+	fn iter_tag_is_in_registry_order() {
+		let mut registry = registry(&["test:a", "test:b", "test:c", "test:d", "test:e"]);
+		let tag = RegistryId::from("tag:tagged");
+
+		//tagged out of order, and once through an alias
+		registry.alias("test:alias", RegistryId::from("test:b")).unwrap();
+
+		for registry_id in ["test:e", "test:alias", "test:d", "test:a"] {
+			registry.tag(tag.clone(), RegistryId::from(registry_id)).unwrap();
+		}
+
+		let order = |registry: &Registry<Item>| registry.iter_tag(&tag).map(|(_, item)| item.0).collect::<Vec<_>>();
+
+		assert_eq!(order(&registry), [0, 1, 3, 4]);
+		assert_eq!(order(&registry), order(&registry.clone()));
+
+		registry.remove(RegistryId::from("test:c"));
+
+		assert_eq!(order(&registry), [0, 1, 3, 4]);
+		assert_eq!(registry.iter_tag(RegistryId::from("tag:missing")).count(), 0);
+	}
+
+	#[test]
+	fn re_source_errors_in_index_order() {
+		let mut registry = registry(&["old:a", "old:b", "old:c", "old:d", "new:c", "new:b"]);
+		registry.alias("old:alias", RegistryId::from("old:a")).unwrap();
+
+		let original = registry.clone();
+		let errors: Vec<(usize, RegistryId)> = registry
+			.re_source("old", "new")
+			.unwrap_err()
+			.into_errors()
+			.into_iter()
+			.map(|(index, error)| match error {
+				RegistryError::DuplicateId(registry_id) => (index, registry_id),
+				other => panic!("expected a duplicate id, found {other:?}"),
+			})
+			.collect();
+
+		assert_eq!(errors, [(1, RegistryId::from("new:b")), (2, RegistryId::from("new:c"))]);
+
+		assert!(registry.diff(&original).is_empty());
+
+		//aliases of the same item are ordered by id
+		let indices: Vec<usize> = registry.re_source("old", "New").unwrap_err().into_errors().into_iter().map(|(index, _)| index).collect();
+
+		assert_eq!(indices, [0, 0, 1, 2, 3]);
+
+		registry.remove(RegistryId::from("new:b"));
+		registry.remove(RegistryId::from("new:c"));
+		registry.re_source("old", "new").unwrap();
+
+		assert_eq!(registry.get_str("new:alias"), Some(&Item(0)));
+		assert_eq!(registry.id_of(3), Some(&RegistryId::from("new:d")));
+		assert!(registry.ids().keys().all(|registry_id| registry_id.source() == "new"));
+	}
+}
@@ -1,21 +1,135 @@
 //! See [`Sign`].
 
+use std::borrow::{Borrow, BorrowMut};
+use std::cmp::Ordering;
 use std::fmt::{Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
-use std::ops::{Deref, DerefMut};
+use std::ops::{Add, AddAssign, Deref, DerefMut, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+use bevy::app::App;
+use bevy::asset::io::Reader;
+use bevy::asset::{Asset, AssetLoader, LoadContext, UntypedAssetId, VisitAssetDependencies};
 use bevy::ecs::component::{ComponentHooks, StorageType};
+use bevy::ecs::event::{EventReader, EventWriter};
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::ecs::world::{FromWorld, World};
 use bevy::prelude::{Component, Event, Resource};
+use bevy::reflect::TypePath;
+use bevy::state::state::{FreelyMutableState, States, SubStates};
+
+/// Declares zero-sized phantom structs and [`Sign`] type aliases using them.
+/// Attributes and doc comments are applied to the phantom struct.
+/// ```ignore
+/// sign! {
+///     /// Marks the mouse wheel accumulated for the fixed schedule.
+///     pub FixedWheel: Vec2 as FixedMouseWheel;
+///     pub(crate) Pause: MenuState as PauseMenuState;
+/// }
+/// ```
+/// Expands to `pub struct FixedWheel;` and `pub type FixedMouseWheel = Sign<Vec2, FixedWheel>;` for the first declaration.
+#[macro_export]
+macro_rules! sign {
+	($($(#[$attribute:meta])* $visibility:vis $phantom:ident: $inner:ty as $alias:ident);+ $(;)?) => {
+		$(
+			$(#[$attribute])*
+			$visibility struct $phantom;
+
+			#[doc = concat!("`Sign` of `", stringify!($inner), "` with the [`", stringify!($phantom), "`] phantom.")]
+			$visibility type $alias = $crate::sign::Sign<$inner, $phantom>;
+		)+
+	};
+}
+
+/// Adds [`BridgeSignEvents::bridge_sign_events`] to [`App`].
+pub trait BridgeSignEvents {
+	/// Adds both events and a [`resign_events`] system to the `schedule`,
+	/// so every `Sign<T, FromPhantom>` event is also sent as a `Sign<T, ToPhantom>` event.
+	fn bridge_sign_events<T, FromPhantom, ToPhantom>(&mut self, schedule: impl ScheduleLabel) -> &mut Self
+	where
+		T: Event + Clone,
+		FromPhantom: Send + Sync + 'static + ?Sized,
+		ToPhantom: Send + Sync + 'static + ?Sized;
+}
+
+impl BridgeSignEvents for App {
+	fn bridge_sign_events<T, FromPhantom, ToPhantom>(&mut self, schedule: impl ScheduleLabel) -> &mut Self
+	where
+		T: Event + Clone,
+		FromPhantom: Send + Sync + 'static + ?Sized,
+		ToPhantom: Send + Sync + 'static + ?Sized,
+	{
+		self.add_event::<Sign<T, FromPhantom>>()
+			.add_event::<Sign<T, ToPhantom>>()
+			.add_systems(schedule, resign_events::<T, FromPhantom, ToPhantom>)
+	}
+}
+
+/// Wraps the `loader` so it loads `Sign<L::Asset, Phantom>` assets, using the same extensions and settings.
+/// Loads with a known asset type like `asset_server.load::<Sign<Image, Lightmap>>(path)` pick this over the wrapped loader.
+/// The signed asset needs its own collection, added with `init_asset`.
+/// ```ignore
+/// app.init_asset::<Sign<Image, Lightmap>>()
+///     .register_asset_loader(SignAssetLoader::<_, Lightmap>::new(ImageLoader::new(CompressedImageFormats::NONE)));
+///
+/// let lightmap: Handle<Sign<Image, Lightmap>> = asset_server.load("lightmaps/cave.png");
+/// ```
+pub struct SignAssetLoader<L, Phantom: ?Sized> {
+	pub loader: L,
+	phantom: PhantomData<Phantom>,
+}
+
+impl<L, Phantom: ?Sized> SignAssetLoader<L, Phantom> {
+	pub fn new(loader: L) -> Self {
+		Self {
+			loader,
+			phantom: PhantomData,
+		}
+	}
+}
+
+impl<L: AssetLoader, Phantom: TypePath + Send + Sync + ?Sized> AssetLoader for SignAssetLoader<L, Phantom> {
+	type Asset = Sign<L::Asset, Phantom>;
+	type Settings = L::Settings;
+	type Error = L::Error;
+
+	async fn load<'a>(&'a self, reader: &'a mut Reader<'_>, settings: &'a L::Settings, load_context: &'a mut LoadContext<'_>) -> Result<Self::Asset, Self::Error> {
+		self.loader.load(reader, settings, load_context).await.map(Sign::new)
+	}
+
+	fn extensions(&self) -> &[&str] {
+		self.loader.extensions()
+	}
+}
+
+/// Allows `app.init_asset_loader::<SignAssetLoader<L, Phantom>>()` for loaders which can be created from the world.
+impl<L: FromWorld, Phantom: ?Sized> FromWorld for SignAssetLoader<L, Phantom> {
+	fn from_world(world: &mut World) -> Self {
+		Self::new(L::from_world(world))
+	}
+}
 
 /// Wrapper for changing the type ID of an existing type.
 /// This can be used in your Bevy game to:
 /// - Create multiple global resource instances of "the same type"
 /// - Create an event with a primitive type
 /// - Add additional context to a resource
+/// - Run independent state machines of the same [`States`] type
+///
 /// E.g. a resource like `Res<MyAssets>` can have two additional versions by using
 /// `Res<Sign<MyAssets, RandomType>>` and `Res<Sign<MyAssets, AnotherType>>`
 ///
+/// With states, `app.init_state::<Sign<MenuState, Pause>>()` and `app.init_state::<Sign<MenuState, Inventory>>()`
+/// are separate, so schedules like `OnEnter(Sign::<MenuState, Pause>::new(MenuState::Open))` and
+/// resources like `NextState<Sign<MenuState, Pause>>` only concern the one with the same `Phantom`.
+///
+/// With assets, `app.init_asset::<Sign<Image, Lightmap>>()` adds a separate `Assets<Sign<Image, Lightmap>>` collection,
+/// loaded by registering a [`SignAssetLoader`] wrapping the loader of the inner asset.
+/// The phantom must implement [`TypePath`] for this.
+///
 /// The `Phantom` generic can be any type you want,
 /// but it is preferred if the type is unique and zero-sized.
+#[derive(TypePath)]
 pub struct Sign<T, Phantom: ?Sized> {
 	inner: T,
 	phantom: PhantomData<Phantom>,
@@ -23,6 +137,16 @@ pub struct Sign<T, Phantom: ?Sized> {
 
 #[allow(dead_code)]
 impl<T, Phantom: ?Sized> Sign<T, Phantom> {
+	/// Returns a reference to the value, for when deref coercion is ambiguous.
+	pub fn as_inner(&self) -> &T {
+		&self.inner
+	}
+
+	/// Returns a mutable reference to the value, for when deref coercion is ambiguous.
+	pub fn as_inner_mut(&mut self) -> &mut T {
+		&mut self.inner
+	}
+
 	/// Creates a `Sign<T>` for any value that can be converted into `T`.
 	pub fn from(value: impl Into<T>) -> Sign<T, Phantom> {
 		Sign {
@@ -31,6 +155,21 @@ impl<T, Phantom: ?Sized> Sign<T, Phantom> {
 		}
 	}
 
+	/// Transforms the value with `f`, keeping the same phantom data.
+	/// ```
+	/// # use bevy_cryotheum::sign::Sign;
+	/// struct Score;
+	///
+	/// let score: Sign<String, Score> = Sign::<u32, Score>::new(2).map(|score| (score * 10).to_string());
+	/// assert_eq!(score, String::from("20"));
+	/// ```
+	pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Sign<U, Phantom> {
+		Sign {
+			phantom: PhantomData,
+			inner: f(self.inner),
+		}
+	}
+
 	/// Change the phantom data to a different type, keeping the same data.
 	pub fn map_phantom<NewPhantom>(self) -> Sign<T, NewPhantom> {
 		Sign {
@@ -53,6 +192,32 @@ impl<T, Phantom: ?Sized> Sign<T, Phantom> {
 		self.phantom
 	}
 
+	/// Replaces the value with `value`, returning the old value.
+	/// ```
+	/// # use bevy_cryotheum::sign::Sign;
+	/// struct Score;
+	///
+	/// let mut score = Sign::<u32, Score>::new(2);
+	/// assert_eq!(score.replace(5), 2);
+	/// assert_eq!(score, 5);
+	/// ```
+	pub fn replace(&mut self, value: T) -> T {
+		std::mem::replace(&mut self.inner, value)
+	}
+
+	/// Replaces the value with `value`, dropping the old value.
+	/// ```
+	/// # use bevy_cryotheum::sign::Sign;
+	/// struct Score;
+	///
+	/// let mut score = Sign::<u32, Score>::new(2);
+	/// score.set(5);
+	/// assert_eq!(score, 5);
+	/// ```
+	pub fn set(&mut self, value: T) {
+		self.inner = value;
+	}
+
 	/// Same as Sign::new but with a type generic for the Phantom generic.
 	pub fn signed<WithPhantom>(value: T) -> Sign<T, WithPhantom> {
 		Sign {
@@ -72,6 +237,18 @@ impl<T, Phantom: ?Sized> Sign<T, Phantom> {
 }
 
 //unitraits!
+impl<T, Phantom: ?Sized> Borrow<T> for Sign<T, Phantom> {
+	fn borrow(&self) -> &T {
+		&self.inner
+	}
+}
+
+impl<T, Phantom: ?Sized> BorrowMut<T> for Sign<T, Phantom> {
+	fn borrow_mut(&mut self) -> &mut T {
+		&mut self.inner
+	}
+}
+
 impl<T, Phantom: ?Sized> Deref for Sign<T, Phantom> {
 	type Target = T;
 
@@ -118,6 +295,8 @@ where
 	}
 }
 
+impl<T: Asset, Phantom: TypePath + Send + Sync + ?Sized> Asset for Sign<T, Phantom> {}
+
 impl<T: Clone, Phantom: ?Sized> Clone for Sign<T, Phantom> {
 	fn clone(&self) -> Self {
 		Self {
@@ -147,12 +326,73 @@ impl<T: Debug, Phantom: ?Sized> Debug for Sign<T, Phantom> {
 	}
 }
 
-impl<T: Default, Phantom: ?Sized> Default for Sign<T, Phantom> {
-	fn default() -> Self {
-		Self {
-			phantom: PhantomData,
-			inner: T::default(),
-		}
+impl<T: Eq, Phantom: ?Sized> Eq for Sign<T, Phantom> {}
+
+impl<T: FreelyMutableState, Phantom: Send + Sync + 'static + ?Sized> FreelyMutableState for Sign<T, Phantom> {}
+
+/// Used by `init_resource` and `init_state` in place of `Default`, which would conflict with bevy's `FromWorld` for every `Default` type.
+/// Every `T: Default` is also `FromWorld`, so this covers both, but `Sign::new(T::default())` is needed outside of a `World`.
+impl<T: FromWorld, Phantom: ?Sized> FromWorld for Sign<T, Phantom> {
+	fn from_world(world: &mut World) -> Self {
+		Sign::new(T::from_world(world))
+	}
+}
+
+impl<T: FromStr, Phantom: ?Sized> FromStr for Sign<T, Phantom> {
+	type Err = T::Err;
+
+	fn from_str(string: &str) -> Result<Self, Self::Err> {
+		T::from_str(string).map(Sign::new)
+	}
+}
+
+impl<T: Hash, Phantom: ?Sized> Hash for Sign<T, Phantom> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.inner.hash(state)
+	}
+}
+
+impl<T: Ord, Phantom: ?Sized> Ord for Sign<T, Phantom> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.inner.cmp(&other.inner)
+	}
+}
+
+impl<T: PartialEq, Phantom: ?Sized> PartialEq for Sign<T, Phantom> {
+	fn eq(&self, other: &Self) -> bool {
+		self.inner == other.inner
+	}
+}
+
+/// Compares with the raw value, e.g. `volume == 1.`
+impl<T: PartialEq, Phantom: ?Sized> PartialEq<T> for Sign<T, Phantom> {
+	fn eq(&self, other: &T) -> bool {
+		self.inner == *other
+	}
+}
+
+impl<T: PartialOrd, Phantom: ?Sized> PartialOrd for Sign<T, Phantom> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		self.inner.partial_cmp(&other.inner)
+	}
+}
+
+/// Compares with the raw value, e.g. `volume < 1.`
+impl<T: PartialOrd, Phantom: ?Sized> PartialOrd<T> for Sign<T, Phantom> {
+	fn partial_cmp(&self, other: &T) -> Option<Ordering> {
+		self.inner.partial_cmp(other)
+	}
+}
+
+impl<T: States, Phantom: Send + Sync + 'static + ?Sized> States for Sign<T, Phantom> {
+	const DEPENDENCY_DEPTH: usize = <T as States>::DEPENDENCY_DEPTH;
+}
+
+impl<T: SubStates, Phantom: Send + Sync + 'static + ?Sized> SubStates for Sign<T, Phantom> {
+	type SourceStates = <T as SubStates>::SourceStates;
+
+	fn should_exist(sources: Self::SourceStates) -> Option<Self> {
+		<T as SubStates>::should_exist(sources).map(Sign::new)
 	}
 }
 
@@ -162,6 +402,373 @@ impl<T: Display, Phantom: ?Sized> Display for Sign<T, Phantom> {
 	}
 }
 
+impl<T: VisitAssetDependencies, Phantom: ?Sized> VisitAssetDependencies for Sign<T, Phantom> {
+	fn visit_dependencies(&self, visit: &mut impl FnMut(UntypedAssetId)) {
+		self.inner.visit_dependencies(visit)
+	}
+}
+
 impl<T: Event, Phantom: Send + Sync + 'static + ?Sized> Event for Sign<T, Phantom> {}
 
 impl<T: Resource, Phantom: Send + Sync + 'static + ?Sized> Resource for Sign<T, Phantom> {}
+
+//operator traits, keeping the phantom through arithmetic with another sign or the raw value
+macro_rules! impl_operators {
+	($($operator:ident::$method:ident, $assign_operator:ident::$assign_method:ident);+) => {
+		$(
+			impl<T: $operator, Phantom: ?Sized> $operator for Sign<T, Phantom> {
+				type Output = Sign<T::Output, Phantom>;
+
+				fn $method(self, rhs: Self) -> Self::Output {
+					Sign::new(self.inner.$method(rhs.inner))
+				}
+			}
+
+			impl<T: $operator, Phantom: ?Sized> $operator<T> for Sign<T, Phantom> {
+				type Output = Sign<T::Output, Phantom>;
+
+				fn $method(self, rhs: T) -> Self::Output {
+					Sign::new(self.inner.$method(rhs))
+				}
+			}
+
+			impl<T: $assign_operator, Phantom: ?Sized> $assign_operator for Sign<T, Phantom> {
+				fn $assign_method(&mut self, rhs: Self) {
+					self.inner.$assign_method(rhs.inner)
+				}
+			}
+
+			impl<T: $assign_operator, Phantom: ?Sized> $assign_operator<T> for Sign<T, Phantom> {
+				fn $assign_method(&mut self, rhs: T) {
+					self.inner.$assign_method(rhs)
+				}
+			}
+		)+
+	};
+}
+
+impl_operators!(
+	Add::add, AddAssign::add_assign;
+	Div::div, DivAssign::div_assign;
+	Mul::mul, MulAssign::mul_assign;
+	Sub::sub, SubAssign::sub_assign
+);
+
+impl<T: Neg, Phantom: ?Sized> Neg for Sign<T, Phantom> {
+	type Output = Sign<T::Output, Phantom>;
+
+	fn neg(self) -> Self::Output {
+		Sign::new(-self.inner)
+	}
+}
+
+/// System sending a clone of every `Sign<T, FromPhantom>` event as a `Sign<T, ToPhantom>` event.
+/// The events are read instead of drained, so other readers of `Sign<T, FromPhantom>` still receive them.
+pub fn resign_events<T, FromPhantom, ToPhantom>(mut reader: EventReader<Sign<T, FromPhantom>>, mut writer: EventWriter<Sign<T, ToPhantom>>)
+where
+	T: Event + Clone,
+	FromPhantom: Send + Sync + 'static + ?Sized,
+	ToPhantom: Send + Sync + 'static + ?Sized,
+{
+	writer.send_batch(reader.read().map(|event| Sign::new(event.inner.clone())));
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	struct Score;
+
+	#[test]
+	fn operators_keep_phantom() {
+		let score = Sign::<i32, Score>::new(6);
+
+		let sum: Sign<i32, Score> = score + Sign::new(2);
+		let difference: Sign<i32, Score> = score - 2;
+
+		assert_eq!(sum, 8);
+		assert_eq!(difference, 4);
+		assert_eq!(score * 2, 12);
+		assert_eq!(score / Sign::new(3), 2);
+		assert_eq!(-score, -6);
+
+		let mut assigned = score;
+
+		assigned += 4;
+		assigned -= Sign::new(1);
+		assigned *= 3;
+		assigned /= Sign::new(9);
+
+		assert_eq!(assigned, 3);
+	}
+
+	#[test]
+	fn ordering_with_signs_and_raw_values() {
+		let low = Sign::<f32, Score>::new(0.5);
+		let high = Sign::<f32, Score>::new(1.5);
+
+		assert!(low < high);
+		assert!(low < 1.);
+		assert!(high >= 1.5);
+		assert_eq!(low.partial_cmp(&f32::NAN), None);
+		assert_eq!(Sign::<u8, Score>::new(2).cmp(&Sign::new(1)), Ordering::Greater);
+		assert_eq!(Sign::<u8, Score>::new(2).max(Sign::new(7)), 7);
+	}
+
+	#[test]
+	fn bridge_sign_events_keeps_original_readers() {
+		use bevy::app::Update;
+		use bevy::prelude::{IntoSystemConfigs, ResMut};
+
+		struct Input;
+		struct Replay;
+
+		#[derive(Clone, Event)]
+		struct Press(u32);
+
+		#[derive(Default, Resource)]
+		struct Received {
+			input: Vec<u32>,
+			replay: Vec<u32>,
+		}
+
+		let mut app = App::new();
+
+		app.init_resource::<Received>().bridge_sign_events::<Press, Input, Replay>(Update).add_systems(
+			Update,
+			(
+				(|mut writer: EventWriter<Sign<Press, Input>>| {
+					writer.send_batch([Sign::new(Press(1)), Sign::new(Press(2))]);
+				})
+				.before(resign_events::<Press, Input, Replay>),
+				(|mut reader: EventReader<Sign<Press, Input>>, mut received: ResMut<Received>| {
+					received.input.extend(reader.read().map(|press| press.0));
+				})
+				.after(resign_events::<Press, Input, Replay>),
+				(|mut reader: EventReader<Sign<Press, Replay>>, mut received: ResMut<Received>| {
+					received.replay.extend(reader.read().map(|press| press.0));
+				})
+				.after(resign_events::<Press, Input, Replay>),
+			),
+		);
+
+		app.update();
+
+		let received = app.world().resource::<Received>();
+
+		assert_eq!(received.input, [1, 2]);
+		assert_eq!(received.replay, [1, 2]);
+	}
+
+	#[test]
+	fn init_resource_uses_inner_from_world() {
+		struct Left;
+		struct Right;
+
+		#[derive(Resource)]
+		struct Config(u32);
+
+		#[derive(Resource)]
+		struct Counter(u32);
+
+		#[derive(Default, Resource)]
+		struct Volume(u32);
+
+		impl FromWorld for Counter {
+			fn from_world(world: &mut World) -> Self {
+				Self(world.resource::<Config>().0)
+			}
+		}
+
+		let mut app = App::new();
+
+		app.insert_resource(Config(5)).init_resource::<Sign<Counter, Left>>();
+		app.world_mut().resource_mut::<Config>().0 = 7;
+		app.init_resource::<Sign<Counter, Right>>().init_resource::<Sign<Volume, Left>>();
+
+		assert_eq!(app.world().resource::<Sign<Counter, Left>>().0, 5);
+		assert_eq!(app.world().resource::<Sign<Counter, Right>>().0, 7);
+
+		//Default types are FromWorld too
+		assert_eq!(app.world().resource::<Sign<Volume, Left>>().0, 0);
+	}
+
+	#[test]
+	fn map_replace_and_set() {
+		use std::rc::Rc;
+
+		let mapped: Sign<Vec<u8>, Score> = Sign::new("abc").map(|str: &str| str.bytes().collect());
+
+		assert_eq!(mapped, vec![b'a', b'b', b'c']);
+
+		//replace returns the old value, set drops it
+		let first = Rc::new(1);
+		let mut sign = Sign::<Rc<u8>, Score>::new(Rc::clone(&first));
+		let replaced = sign.replace(Rc::new(2));
+
+		assert!(Rc::ptr_eq(&replaced, &first));
+		assert_eq!(**sign, 2);
+
+		drop(replaced);
+		sign.set(Rc::clone(&first));
+
+		assert_eq!(Rc::strong_count(&first), 2);
+
+		sign.set(Rc::new(3));
+
+		assert_eq!(Rc::strong_count(&first), 1);
+		assert_eq!(**sign, 3);
+	}
+
+	mod declared {
+		use std::collections::BTreeMap;
+
+		crate::sign! {
+			/// Documented phantom with derives.
+			#[derive(Clone, Copy, Debug, Default)]
+			pub Public: BTreeMap<String, Vec<u32>> as PublicMap;
+			pub(crate) Crate: Option<(u8, u16)> as CrateOption;
+			Private: u8 as PrivateByte;
+		}
+
+		//private declarations are usable inside their module
+		pub fn private_byte() -> u8 {
+			*PrivateByte::new(3)
+		}
+	}
+
+	#[test]
+	fn sign_macro_declarations() {
+		let public: declared::PublicMap = Sign::new(From::from([(String::from("a"), vec![1])]));
+		let signed_option = declared::CrateOption::new(Some((1, 2)));
+
+		assert_eq!(public["a"], [1]);
+		assert_eq!(signed_option, Some((1, 2)));
+		assert_eq!(declared::private_byte(), 3);
+		assert_eq!(format!("{:?}", declared::Public), "Public");
+	}
+
+	#[test]
+	fn signed_states_are_independent() {
+		use bevy::app::Update;
+		use bevy::prelude::{AppExtStates, NextState, OnEnter, ResMut, State};
+		use bevy::state::app::StatesPlugin;
+
+		#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, States)]
+		enum MenuState {
+			#[default]
+			Closed,
+			Open,
+		}
+
+		struct Pause;
+		struct Inventory;
+
+		#[derive(Default, Resource)]
+		struct Entered(u32);
+
+		let mut app = App::new();
+
+		app.add_plugins(StatesPlugin)
+			.init_state::<Sign<MenuState, Pause>>()
+			.init_state::<Sign<MenuState, Inventory>>()
+			.init_resource::<Entered>()
+			.add_systems(OnEnter(Sign::<MenuState, Pause>::new(MenuState::Open)), |mut entered: ResMut<Entered>| entered.0 += 1)
+			.add_systems(OnEnter(Sign::<MenuState, Inventory>::new(MenuState::Open)), |mut entered: ResMut<Entered>| entered.0 += 10)
+			.add_systems(Update, |mut next: ResMut<NextState<Sign<MenuState, Pause>>>| next.set(Sign::new(MenuState::Open)));
+
+		app.update();
+		app.update();
+
+		assert_eq!(**app.world().resource::<State<Sign<MenuState, Pause>>>().get(), MenuState::Open);
+		assert_eq!(**app.world().resource::<State<Sign<MenuState, Inventory>>>().get(), MenuState::Closed);
+
+		//OnEnter only ran once for the pause menu, and never for the inventory
+		assert_eq!(app.world().resource::<Entered>().0, 1);
+	}
+
+	#[test]
+	fn signed_assets_have_their_own_collection() {
+		use bevy::asset::{AssetApp, AssetPlugin, AssetServer, Assets, AsyncReadExt, Handle};
+		use bevy::MinimalPlugins;
+
+		#[derive(Asset, TypePath)]
+		struct Text(String);
+
+		#[derive(TypePath)]
+		struct Shouted;
+
+		#[derive(Default)]
+		struct TextLoader;
+
+		impl AssetLoader for TextLoader {
+			type Asset = Text;
+			type Settings = ();
+			type Error = std::io::Error;
+
+			async fn load<'a>(&'a self, reader: &'a mut Reader<'_>, _settings: &'a (), _load_context: &'a mut LoadContext<'_>) -> Result<Text, Self::Error> {
+				let mut text = String::new();
+				reader.read_to_string(&mut text).await?;
+
+				Ok(Text(text))
+			}
+
+			fn extensions(&self) -> &[&str] {
+				&["txt"]
+			}
+		}
+
+		let dir = std::env::temp_dir().join(format!("bevy_cryotheum_{}_signed_assets", std::process::id()));
+		std::fs::create_dir_all(&dir).unwrap();
+		std::fs::write(dir.join("greeting.txt"), "hello").unwrap();
+
+		let mut app = App::new();
+
+		app.add_plugins((
+			MinimalPlugins,
+			AssetPlugin {
+				file_path: dir.to_string_lossy().into_owned(),
+				..Default::default()
+			},
+		))
+		.init_asset::<Text>()
+		.init_asset::<Sign<Text, Shouted>>()
+		.init_asset_loader::<TextLoader>()
+		.init_asset_loader::<SignAssetLoader<TextLoader, Shouted>>();
+
+		let asset_server = app.world().resource::<AssetServer>();
+		let plain: Handle<Text> = asset_server.load("greeting.txt");
+		let signed: Handle<Sign<Text, Shouted>> = asset_server.load("greeting.txt");
+
+		for _ in 0..1000 {
+			app.update();
+
+			if app.world().resource::<Assets<Sign<Text, Shouted>>>().contains(&signed) && app.world().resource::<Assets<Text>>().contains(&plain) {
+				break;
+			}
+
+			std::thread::sleep(std::time::Duration::from_millis(5));
+		}
+
+		assert_eq!(app.world().resource::<Assets<Text>>().get(&plain).unwrap().0, "hello");
+		assert_eq!(app.world().resource::<Assets<Sign<Text, Shouted>>>().get(&signed).unwrap().0, "hello");
+		assert_eq!(app.world().resource::<Assets<Text>>().len(), 1);
+
+		std::fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[test]
+	fn from_str_parses_inner() {
+		let parsed: Sign<u64, Score> = "5".parse().unwrap();
+
+		assert_eq!(parsed, 5);
+		assert_eq!("18446744073709551615".parse::<Sign<u64, Score>>().unwrap(), u64::MAX);
+
+		//the error is the one of the inner type
+		let error: std::num::ParseIntError = "five".parse::<Sign<u64, Score>>().unwrap_err();
+
+		assert_eq!(error, "five".parse::<u64>().unwrap_err());
+		assert!(matches!("-5".parse::<Sign<u64, Score>>().unwrap_err().kind(), std::num::IntErrorKind::InvalidDigit));
+		assert!(matches!("".parse::<Sign<u64, Score>>().unwrap_err().kind(), std::num::IntErrorKind::Empty));
+	}
+}
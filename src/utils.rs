@@ -4,6 +4,7 @@ use std::hash::Hash;
 use std::path::PathBuf;
 use std::sync::LazyLock;
 use bevy::input::ButtonInput;
+use bevy::math::Vec2;
 
 /// Array where the index is an ASCII character's byte representation,
 /// and the value is true if it is considered alpha numeric.
@@ -70,6 +71,81 @@ where
 	}
 }
 
+/// Two dimensional version of [`CommonSign`], with each axis following the same rules.
+/// Implemented on `[bool; 4]` and `(&ButtonInput<T>, [T; 4])` in left, right, down, up order.
+pub trait CommonSign2d {
+	/// Returns a `Vec2` where each axis is 1f32, 0f32, or -1f32 like [`CommonSign::common_sign`].
+	fn common_sign_2d(&self) -> Vec2;
+
+	/// Same as [`CommonSign2d::common_sign_2d`] but normalized, so diagonals aren't longer than a single axis.
+	fn common_sign_2d_normalized(&self) -> Vec2 {
+		self.common_sign_2d().normalize_or_zero()
+	}
+}
+
+impl CommonSign2d for [bool; 4] {
+	/// [left, right, down, up]
+	fn common_sign_2d(&self) -> Vec2 {
+		let [left, right, down, up] = *self;
+
+		Vec2::new((left, right).common_sign(), (down, up).common_sign())
+	}
+}
+
+impl<T> CommonSign2d for (&ButtonInput<T>, [T; 4])
+where
+	T: Copy + Eq + Hash + Send + Sync + 'static,
+{
+	/// [left, right, down, up]
+	fn common_sign_2d(&self) -> Vec2 {
+		self.1.map(|button| self.0.pressed(button)).common_sign_2d()
+	}
+}
+
+/// Keyboard shortcut style helpers for [`ButtonInput`], where a `key` is used while holding `modifiers`.
+/// Extra buttons held alongside a chord are allowed, use the `_exact` methods to reject them.
+pub trait ChordInput<T> {
+	/// True if every button of the `modifiers` and the `key` are pressed.
+	fn chord_pressed(&self, modifiers: &[T], key: T) -> bool;
+
+	/// Same as [`ChordInput::chord_pressed`] but no other buttons may be pressed.
+	fn chord_pressed_exact(&self, modifiers: &[T], key: T) -> bool;
+
+	/// True if every button of the `modifiers` are pressed and the `key` was just pressed.
+	fn chord_just_pressed(&self, modifiers: &[T], key: T) -> bool;
+
+	/// Same as [`ChordInput::chord_just_pressed`] but no other buttons may be pressed.
+	fn chord_just_pressed_exact(&self, modifiers: &[T], key: T) -> bool;
+
+	/// True if any of the `chords` were just pressed, see [`ChordInput::chord_just_pressed`].
+	fn any_chord_just_pressed(&self, chords: &[(&[T], T)]) -> bool;
+}
+
+impl<T> ChordInput<T> for ButtonInput<T>
+where
+	T: Copy + Eq + Hash + Send + Sync + 'static,
+{
+	fn chord_pressed(&self, modifiers: &[T], key: T) -> bool {
+		self.pressed(key) && self.all_pressed(modifiers.iter().copied())
+	}
+
+	fn chord_pressed_exact(&self, modifiers: &[T], key: T) -> bool {
+		self.chord_pressed(modifiers, key) && self.get_pressed().all(|button| *button == key || modifiers.contains(button))
+	}
+
+	fn chord_just_pressed(&self, modifiers: &[T], key: T) -> bool {
+		self.just_pressed(key) && self.all_pressed(modifiers.iter().copied())
+	}
+
+	fn chord_just_pressed_exact(&self, modifiers: &[T], key: T) -> bool {
+		self.chord_just_pressed(modifiers, key) && self.get_pressed().all(|button| *button == key || modifiers.contains(button))
+	}
+
+	fn any_chord_just_pressed(&self, chords: &[(&[T], T)]) -> bool {
+		chords.iter().any(|(modifiers, key)| self.chord_just_pressed(modifiers, *key))
+	}
+}
+
 /// For implementing the `is_alpha_numeric` method on various types, which are typically strings.
 pub trait IsAlphaNumeric {
 	/// Comparse each byte against [`ALPHA_NUMERIC_BYTES`].
@@ -99,3 +175,176 @@ impl IsAlphaNumeric for usize {
 		ALPHA_NUMERIC_BYTES[*self]
 	}
 }
+
+/// The number of single character insertions, deletions, or substitutions needed to turn `a` into `b`.
+/// Useful for suggesting the closest valid name when one is misspelled.
+pub fn levenshtein_distance(a: &str, b: &str) -> usize {
+	let b_chars: Vec<char> = b.chars().collect();
+	let mut row: Vec<usize> = (0..=b_chars.len()).collect();
+
+	for (a_index, a_char) in a.chars().enumerate() {
+		let mut diagonal = row[0];
+		row[0] = a_index + 1;
+
+		for (b_index, b_char) in b_chars.iter().enumerate() {
+			let substitution = diagonal + usize::from(a_char != *b_char);
+			diagonal = row[b_index + 1];
+			row[b_index + 1] = substitution.min(row[b_index] + 1).min(diagonal + 1);
+		}
+	}
+
+	row[b_chars.len()]
+}
+
+/// Converts a display name like `"Iron Sword!"` into a name usable in a `RegistryId` like `"iron_sword"`.
+/// Whitespace becomes `_`, other bytes not in [`ALPHA_NUMERIC_BYTES`] are removed,
+/// and an `n` is prefixed if the name would start with a digit.
+/// Returns `None` if no letters or digits remain.
+pub fn sanitize_registry_name(display: &str) -> Option<String> {
+	let mut name = String::with_capacity(display.len());
+
+	for char in display.trim().to_lowercase().chars() {
+		if char.is_whitespace() {
+			name.push('_');
+		} else if char.is_ascii() && (char as u8).is_alpha_numeric() {
+			name.push(char);
+		}
+	}
+
+	let name = name.trim_start_matches('_');
+
+	if !name.bytes().any(|byte| byte.is_ascii_alphanumeric()) {
+		return None;
+	}
+
+	if name.as_bytes()[0].is_ascii_digit() {
+		Some(format!("n{name}"))
+	} else {
+		Some(name.to_owned())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bevy::input::keyboard::KeyCode;
+
+	const ARROWS: [KeyCode; 4] = [KeyCode::ArrowLeft, KeyCode::ArrowRight, KeyCode::ArrowDown, KeyCode::ArrowUp];
+
+	/// All 16 `[left, right, down, up]` combinations.
+	fn combinations() -> impl Iterator<Item = [bool; 4]> {
+		(0..16u8).map(|bits| [0, 1, 2, 3].map(|bit| bits & (1 << bit) != 0))
+	}
+
+	fn axis(negative: bool, positive: bool) -> f32 {
+		match (negative, positive) {
+			(true, false) => -1.,
+			(false, true) => 1.,
+			_ => 0.,
+		}
+	}
+
+	#[test]
+	fn common_sign_2d_combinations() {
+		for buttons @ [left, right, down, up] in combinations() {
+			let expected = Vec2::new(axis(left, right), axis(down, up));
+
+			assert_eq!(buttons.common_sign_2d(), expected, "{buttons:?}");
+
+			let normalized = buttons.common_sign_2d_normalized();
+			assert_eq!(normalized, expected.normalize_or_zero(), "{buttons:?}");
+
+			if expected == Vec2::ZERO {
+				assert_eq!(normalized, Vec2::ZERO, "{buttons:?}");
+			} else {
+				assert!((normalized.length() - 1.).abs() < 1e-6, "{buttons:?}");
+			}
+		}
+	}
+
+	#[test]
+	fn common_sign_2d_button_input() {
+		for buttons in combinations() {
+			let mut input = ButtonInput::<KeyCode>::default();
+
+			for (key, pressed) in ARROWS.iter().zip(buttons) {
+				if pressed {
+					input.press(*key);
+				}
+			}
+
+			assert_eq!((&input, ARROWS).common_sign_2d(), buttons.common_sign_2d(), "{buttons:?}");
+			assert_eq!((&input, ARROWS).common_sign_2d_normalized(), buttons.common_sign_2d_normalized(), "{buttons:?}");
+		}
+	}
+
+	#[test]
+	fn chord_pressed() {
+		let mut input = ButtonInput::<KeyCode>::default();
+		input.press(KeyCode::ControlLeft);
+		input.press(KeyCode::KeyS);
+
+		assert!(input.chord_pressed(&[KeyCode::ControlLeft], KeyCode::KeyS));
+		assert!(input.chord_pressed(&[], KeyCode::KeyS));
+		assert!(!input.chord_pressed(&[KeyCode::ControlLeft, KeyCode::ShiftLeft], KeyCode::KeyS));
+		assert!(!input.chord_pressed(&[KeyCode::ControlLeft], KeyCode::KeyZ));
+
+		//extra buttons are only rejected by the exact version
+		input.press(KeyCode::ShiftLeft);
+		assert!(input.chord_pressed(&[KeyCode::ControlLeft], KeyCode::KeyS));
+		assert!(!input.chord_pressed_exact(&[KeyCode::ControlLeft], KeyCode::KeyS));
+		assert!(input.chord_pressed_exact(&[KeyCode::ControlLeft, KeyCode::ShiftLeft], KeyCode::KeyS));
+	}
+
+	#[test]
+	fn chord_just_pressed() {
+		let mut input = ButtonInput::<KeyCode>::default();
+		input.press(KeyCode::ControlLeft);
+		input.press(KeyCode::KeyS);
+
+		assert!(input.chord_just_pressed(&[KeyCode::ControlLeft], KeyCode::KeyS));
+		assert!(input.chord_just_pressed_exact(&[KeyCode::ControlLeft], KeyCode::KeyS));
+
+		//the key is still held but no longer just pressed
+		input.clear();
+		assert!(input.chord_pressed(&[KeyCode::ControlLeft], KeyCode::KeyS));
+		assert!(!input.chord_just_pressed(&[KeyCode::ControlLeft], KeyCode::KeyS));
+
+		//pressing the modifier after the key does not count
+		let mut input = ButtonInput::<KeyCode>::default();
+		input.press(KeyCode::KeyS);
+		input.clear();
+		input.press(KeyCode::ControlLeft);
+		assert!(!input.chord_just_pressed(&[KeyCode::ControlLeft], KeyCode::KeyS));
+
+		//a modifier that is missing fails
+		let mut input = ButtonInput::<KeyCode>::default();
+		input.press(KeyCode::KeyS);
+		assert!(!input.chord_just_pressed(&[KeyCode::ControlLeft], KeyCode::KeyS));
+
+		//extra held buttons
+		input.press(KeyCode::ControlLeft);
+		input.press(KeyCode::AltLeft);
+		assert!(input.chord_just_pressed(&[KeyCode::ControlLeft], KeyCode::KeyS));
+		assert!(!input.chord_just_pressed_exact(&[KeyCode::ControlLeft], KeyCode::KeyS));
+	}
+
+	#[test]
+	fn any_chord_just_pressed() {
+		let save: (&[KeyCode], KeyCode) = (&[KeyCode::ControlLeft], KeyCode::KeyS);
+		let redo: (&[KeyCode], KeyCode) = (&[KeyCode::ControlLeft, KeyCode::ShiftLeft], KeyCode::KeyZ);
+		let mut input = ButtonInput::<KeyCode>::default();
+
+		assert!(!input.any_chord_just_pressed(&[]));
+		assert!(!input.any_chord_just_pressed(&[save, redo]));
+
+		input.press(KeyCode::ControlLeft);
+		input.press(KeyCode::ShiftLeft);
+		input.press(KeyCode::KeyZ);
+		assert!(input.any_chord_just_pressed(&[save, redo]));
+		assert!(!input.any_chord_just_pressed(&[save]));
+
+		input.release(KeyCode::ShiftLeft);
+		assert!(!input.any_chord_just_pressed(&[save, redo]));
+	}
+}